@@ -0,0 +1,16 @@
+//! Prints the number of lints in each lint group, the same as `cargo dev lint_stats`.
+//!
+//! Usage: `lint-stats [PATH]`, where `PATH` is the directory to gather the lints from and defaults
+//! to `clippy_lints/src`.
+
+#![cfg_attr(feature = "deny-warnings", deny(warnings))]
+// warn on lints, that are included in `rust-lang/rust`s bootstrap
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use clippy_dev::update_lints;
+use std::path::PathBuf;
+
+fn main() {
+    let root = std::env::args_os().nth(1).map(PathBuf::from);
+    update_lints::print_lint_stats(root.as_deref());
+}
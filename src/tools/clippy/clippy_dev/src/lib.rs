@@ -14,11 +14,17 @@
 use std::path::PathBuf;
 use std::process::{self, ExitStatus};
 
+#[cfg(feature = "fs")]
 pub mod dogfood;
+#[cfg(feature = "fs")]
 pub mod fmt;
+#[cfg(feature = "fs")]
 pub mod lint;
+#[cfg(feature = "fs")]
 pub mod new_lint;
+#[cfg(feature = "fs")]
 pub mod serve;
+#[cfg(feature = "fs")]
 pub mod setup;
 pub mod update_lints;
 
@@ -45,6 +51,7 @@ pub fn cargo_clippy_path() -> PathBuf {
 ///
 /// Panics if the current directory could not be retrieved, there was an error reading any of the
 /// Cargo.toml files or ancestor directory is the clippy root directory
+#[cfg(feature = "fs")]
 #[must_use]
 pub fn clippy_project_root() -> PathBuf {
     let current_dir = std::env::current_dir().unwrap();
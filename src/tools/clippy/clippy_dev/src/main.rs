@@ -6,6 +6,7 @@
 use clippy_dev::{dogfood, fmt, lint, new_lint, serve, setup, update_lints};
 use indoc::indoc;
 use std::convert::Infallible;
+use std::path::Path;
 
 fn main() {
     let matches = get_clap_config();
@@ -95,6 +96,10 @@ fn main() {
             let uplift = matches.get_flag("uplift");
             update_lints::rename(old_name, new_name, uplift);
         },
+        Some(("lint_stats", matches)) => {
+            let path = matches.get_one::<String>("path");
+            update_lints::print_lint_stats(path.map(Path::new));
+        },
         Some(("deprecate", matches)) => {
             let name = matches.get_one::<String>("name").unwrap();
             let reason = matches.get_one("reason");
@@ -312,6 +317,9 @@ fn get_clap_config() -> ArgMatches {
                     .action(ArgAction::SetTrue)
                     .help("This lint will be uplifted into rustc"),
             ]),
+            Command::new("lint_stats")
+                .about("Prints the number of lints in each lint group")
+                .arg(Arg::new("path").help("The directory to gather the lints from, defaults to `clippy_lints/src`")),
             Command::new("deprecate").about("Deprecates the given lint").args([
                 Arg::new("name")
                     .index(1)
@@ -1,23 +1,41 @@
+#[cfg(feature = "fs")]
 use crate::clippy_project_root;
+#[cfg(feature = "fs")]
 use aho_corasick::AhoCorasickBuilder;
+#[cfg(feature = "fs")]
 use indoc::writedoc;
 use itertools::Itertools;
 use rustc_lexer::{tokenize, unescape, LiteralKind, TokenKind};
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(feature = "fs")]
 use std::ffi::OsStr;
 use std::fmt::{self, Write};
+#[cfg(feature = "fs")]
 use std::fs::{self, OpenOptions};
-use std::io::{self, Read, Seek, SeekFrom, Write as _};
+use std::io::{self, Read};
+#[cfg(feature = "fs")]
+use std::io::{Seek, SeekFrom, Write as _};
 use std::ops::Range;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "fs")]
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(feature = "fs")]
+use std::time::SystemTime;
+#[cfg(feature = "fs")]
 use walkdir::{DirEntry, WalkDir};
 
+#[cfg(feature = "fs")]
 const GENERATED_FILE_COMMENT: &str = "// This file was generated by `cargo dev update_lints`.\n\
      // Use that command to update this file and do not edit by hand.\n\
      // Manual edits will be overwritten.\n\n";
 
 const DOCS_LINK: &str = "https://rust-lang.github.io/rust-clippy/master/index.html";
 
+/// The maximum length of a lint name accepted by `Lint::invalid_lint_names`
+pub const MAX_LINT_NAME_LEN: usize = 50;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum UpdateMode {
     Check,
@@ -33,11 +51,13 @@ pub enum UpdateMode {
 /// # Panics
 ///
 /// Panics if a file path could not read from or then written to
+#[cfg(feature = "fs")]
 pub fn update(update_mode: UpdateMode) {
     let (lints, deprecated_lints, renamed_lints) = gather_all();
     generate_lint_files(update_mode, &lints, &deprecated_lints, &renamed_lints);
 }
 
+#[cfg(feature = "fs")]
 fn generate_lint_files(
     update_mode: UpdateMode,
     lints: &[Lint],
@@ -121,6 +141,7 @@ fn generate_lint_files(
     process_file("tests/ui/rename.rs", update_mode, &content);
 }
 
+#[cfg(feature = "fs")]
 pub fn print_lints() {
     let (lint_list, _, _) = gather_all();
     let usable_lints = Lint::usable_lints(&lint_list);
@@ -133,13 +154,42 @@ pub fn print_lints() {
         lints.sort_by_key(|l| l.name.clone());
 
         for lint in lints {
-            println!("* [{}]({DOCS_LINK}#{}) ({})", lint.name, lint.name, lint.desc);
+            println!("* [{}]({}) ({})", lint.name, lint.url(), lint.desc);
         }
     }
 
     println!("there are {usable_lint_count} lints");
 }
 
+/// Runs the `lint_stats` command.
+///
+/// Prints the number of usable lints in each lint group and the total. `root` overrides the
+/// directory the lints are gathered from, which defaults to `clippy_lints/src`.
+///
+/// # Panics
+///
+/// Panics if a file path could not read from
+#[cfg(feature = "fs")]
+pub fn print_lint_stats(root: Option<&Path>) {
+    let (lints, deprecated_lints, _) = match root {
+        Some(root) => gather_all_from(root),
+        None => gather_all(),
+    };
+    let stats = Lint::compute_stats(&lints, &deprecated_lints);
+
+    let width = stats
+        .group_counts
+        .keys()
+        .map(String::len)
+        .max()
+        .unwrap_or(0)
+        .max("total".len());
+    for (group, count) in &stats.group_counts {
+        println!("{group:width$} {count:>5}");
+    }
+    println!("{:width$} {:>5}", "total", stats.usable);
+}
+
 /// Runs the `rename_lint` command.
 ///
 /// This does the following:
@@ -156,6 +206,7 @@ pub fn print_lints() {
 /// * If `old_name` doesn't name an existing lint.
 /// * If `old_name` names a deprecated or renamed lint.
 #[allow(clippy::too_many_lines)]
+#[cfg(feature = "fs")]
 pub fn rename(old_name: &str, new_name: &str, uplift: bool) {
     if let Some((prefix, _)) = old_name.split_once("::") {
         panic!("`{old_name}` should not contain the `{prefix}` prefix");
@@ -293,7 +344,8 @@ pub fn rename(old_name: &str, new_name: &str, uplift: bool) {
 
         // Don't change `clippy_utils/src/renamed_lints.rs` here as it would try to edit the lint being
         // renamed.
-        for (_, file) in clippy_lints_src_files().filter(|(rel_path, _)| rel_path != OsStr::new("renamed_lints.rs")) {
+        let src_root = clippy_project_root().join("clippy_lints/src");
+        for (_, file) in lint_files(&src_root).filter(|(rel_path, _)| rel_path != OsStr::new("renamed_lints.rs")) {
             rewrite_file(file.path(), |s| replace_ident_like(s, replacements));
         }
 
@@ -304,6 +356,7 @@ pub fn rename(old_name: &str, new_name: &str, uplift: bool) {
     println!("note: `cargo uitest` still needs to be run to update the test results");
 }
 
+#[cfg(feature = "fs")]
 const DEFAULT_DEPRECATION_REASON: &str = "default deprecation note";
 /// Runs the `deprecate` command
 ///
@@ -314,16 +367,14 @@ pub fn rename(old_name: &str, new_name: &str, uplift: bool) {
 /// # Panics
 ///
 /// If a file path could not read from or written to
+#[cfg(feature = "fs")]
 pub fn deprecate(name: &str, reason: Option<&String>) {
-    fn finish(
-        (lints, mut deprecated_lints, renamed_lints): (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>),
-        name: &str,
-        reason: &str,
-    ) {
+    fn finish((lints, mut deprecated_lints, renamed_lints): GatheredLints, name: &str, reason: &str) {
         deprecated_lints.push(DeprecatedLint {
             name: name.to_string(),
             reason: reason.to_string(),
             declaration_range: Range::default(),
+            replacement: None,
         });
 
         generate_lint_files(UpdateMode::Change, &lints, &deprecated_lints, &renamed_lints);
@@ -366,6 +417,7 @@ fn finish(
     eprintln!("error: lint not found");
 }
 
+#[cfg(feature = "fs")]
 fn remove_lint_declaration(name: &str, path: &Path, lints: &mut Vec<Lint>) -> io::Result<bool> {
     fn remove_lint(name: &str, lints: &mut Vec<Lint>) {
         lints.iter().position(|l| l.name == name).map(|pos| lints.remove(pos));
@@ -430,8 +482,10 @@ fn remove_impl_lint_pass(lint_name_upper: &str, content: &mut String) {
                     fs::remove_file(lint_mod_path).ok();
                 }
 
-                let mut content =
+                let content =
                     fs::read_to_string(path).unwrap_or_else(|_| panic!("failed to read `{}`", path.to_string_lossy()));
+                // The declaration range doesn't include the BOM, see `gather_from_file`
+                let mut content = strip_bom(&content).to_string();
 
                 eprintln!(
                     "warn: you will have to manually remove any code related to `{name}` from `{}`",
@@ -465,6 +519,7 @@ fn remove_impl_lint_pass(lint_name_upper: &str, content: &mut String) {
     Ok(false)
 }
 
+#[cfg(feature = "fs")]
 fn declare_deprecated(name: &str, path: &Path, reason: &str) -> io::Result<()> {
     let mut file = OpenOptions::new().write(true).open(path)?;
 
@@ -498,6 +553,7 @@ fn declare_deprecated(name: &str, path: &Path, reason: &str) -> io::Result<()> {
 
 /// Replace substrings if they aren't bordered by identifier characters. Returns `None` if there
 /// were no replacements.
+#[cfg(feature = "fs")]
 fn replace_ident_like(contents: &str, replacements: &[(&str, &str)]) -> Option<String> {
     fn is_ident_char(c: u8) -> bool {
         matches!(c, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_')
@@ -535,6 +591,55 @@ fn round_to_fifty(count: usize) -> usize {
     count / 50 * 50
 }
 
+/// The lint count stated in the README doesn't match the number of usable lints.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CountMismatch {
+    /// The count found in the README, or `None` if there is none
+    pub readme: Option<usize>,
+    /// The number of usable lints rounded down to a multiple of fifty, as written by `update_lints`
+    pub expected: usize,
+}
+
+impl fmt::Display for CountMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.readme {
+            Some(count) => write!(f, "The README states {count} lints, expected {}", self.expected),
+            None => write!(
+                f,
+                "The README doesn't state the number of lints, expected {}",
+                self.expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CountMismatch {}
+
+/// Checks that the README states the number of usable lints, as in "There are over 650 lints
+/// included in this crate!". The number may contain commas, e.g. `1,050`.
+///
+/// # Errors
+///
+/// Returns both counts if the README's count is missing or doesn't match
+pub fn verify_lint_count(readme: &str, lints: &[Lint]) -> Result<(), CountMismatch> {
+    let expected = round_to_fifty(Lint::total_usable(lints));
+    let count = readme.find(" lints included in this crate!").and_then(|end| {
+        let start = readme[..end]
+            .rfind(|c: char| !(c.is_ascii_digit() || c == ','))
+            .map_or(0, |i| i + 1);
+        readme[start..end].replace(',', "").parse::<usize>().ok()
+    });
+    if count == Some(expected) {
+        Ok(())
+    } else {
+        Err(CountMismatch {
+            readme: count,
+            expected,
+        })
+    }
+}
+
+#[cfg(feature = "fs")]
 fn process_file(path: impl AsRef<Path>, update_mode: UpdateMode, content: &str) {
     if update_mode == UpdateMode::Check {
         let old_content =
@@ -548,6 +653,7 @@ fn process_file(path: impl AsRef<Path>, update_mode: UpdateMode, content: &str)
     }
 }
 
+#[cfg(feature = "fs")]
 fn exit_with_failure() {
     println!(
         "Not all lints defined properly. \
@@ -557,440 +663,2926 @@ fn exit_with_failure() {
 }
 
 /// Lint data parsed from the Clippy source code.
+///
+/// With the `serde` feature lints are serialized with an additional `id` field, see `Lint::id`.
 #[derive(Clone, PartialEq, Eq, Debug)]
-struct Lint {
-    name: String,
-    group: String,
-    desc: String,
-    module: String,
-    declaration_range: Range<usize>,
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(remote = "Self")
+)]
+pub struct Lint {
+    pub name: String,
+    pub group: String,
+    pub desc: String,
+    /// The description exactly as written in the source, including the quotes of the string
+    /// literal
+    pub raw_desc: String,
+    pub module: String,
+    pub declaration_range: Range<usize>,
+    /// The 1-based line of the `declare_clippy_lint!` invocation in the module's file, or 0 if the
+    /// lint wasn't parsed from a file
+    pub line: usize,
+    /// The applicability of the lint's suggestions, taken from a
+    /// `#[clippy::applicability = "..."]` attribute, e.g. `MachineApplicable`
+    pub applicability: Option<String>,
+    /// The level the lint is emitted at by default (`allow`, `warn` or `deny`). It's inferred from
+    /// the group the same way `declare_clippy_lint!` does it, e.g. `restriction` and `nursery`
+    /// lints are `allow`, but a `#[clippy::default_level = "..."]` attribute takes precedence.
+    /// It's `None` for unknown groups.
+    pub default_level: Option<String>,
 }
 
 impl Lint {
+    /// Returns a builder for a lint, e.g. for tests
+    #[must_use]
+    pub fn builder() -> LintBuilder {
+        LintBuilder::default()
+    }
+
     #[must_use]
     fn new(name: &str, group: &str, desc: &str, module: &str, declaration_range: Range<usize>) -> Self {
+        Self::new_with_opts(name, group, desc, module, declaration_range, true)
+    }
+
+    /// Creates a lint from its declaration, where `desc` is the description's string literal. The
+    /// name and group are lowercased. If `normalize` is `true`, line splices are removed from the
+    /// description. Otherwise it's kept as written, including line breaks. Either way only the
+    /// quotes are removed and other escapes like `\"` are kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `desc` isn't a string literal
+    #[must_use]
+    pub fn new_with_opts(
+        name: &str,
+        group: &str,
+        desc: &str,
+        module: &str,
+        declaration_range: Range<usize>,
+        normalize: bool,
+    ) -> Self {
+        let desc_value = if normalize {
+            remove_line_splices(desc)
+        } else {
+            strip_quotes(desc).1.into()
+        };
+        let group = group.to_lowercase();
         Self {
             name: name.to_lowercase(),
-            group: group.into(),
-            desc: remove_line_splices(desc),
+            default_level: LintCategory::from_group(&group).default_level().map(Into::into),
+            group,
+            desc: desc_value,
+            raw_desc: desc.into(),
             module: module.into(),
             declaration_range,
+            line: 0,
+            applicability: None,
         }
     }
 
-    /// Returns all non-deprecated lints and non-internal lints
+    /// Creates a lint declared without a description, using its doc comment lines instead
+    fn from_doc_comments(
+        name: &str,
+        group: &str,
+        docs: &[&str],
+        module: &str,
+        declaration_range: Range<usize>,
+    ) -> Self {
+        let group = group.to_lowercase();
+        Self {
+            name: name.to_lowercase(),
+            default_level: LintCategory::from_group(&group).default_level().map(Into::into),
+            group,
+            desc: doc_comments_to_desc(docs),
+            raw_desc: docs.join("\n"),
+            module: module.into(),
+            declaration_range,
+            line: 0,
+            applicability: None,
+        }
+    }
+
+    /// Returns the stable identifier of the lint, its lowercased name. It doesn't change when the
+    /// lint's description or group is edited, so it can be used as a key to store lints by.
     #[must_use]
-    fn usable_lints(lints: &[Self]) -> Vec<Self> {
-        lints
-            .iter()
-            .filter(|l| !l.group.starts_with("internal"))
-            .cloned()
-            .collect()
+    pub fn id(&self) -> String {
+        self.name.to_lowercase()
     }
 
-    /// Returns all internal lints (not `internal_warn` lints)
+    /// Returns the fields of the lint by name, e.g. to fill in a template. Missing optional values
+    /// are empty strings and the declaration range is formatted as `start..end`.
+    ///
+    /// ```
+    /// use clippy_dev::update_lints::Lint;
+    ///
+    /// let lint = Lint::builder().name("ptr_arg").group("style").desc("desc").module("ptr").build();
+    /// let fields = lint.as_fields();
+    /// assert_eq!(
+    ///     vec![
+    ///         "applicability",
+    ///         "declaration_range",
+    ///         "default_level",
+    ///         "desc",
+    ///         "group",
+    ///         "line",
+    ///         "module",
+    ///         "name",
+    ///         "raw_desc",
+    ///     ],
+    ///     fields.keys().copied().collect::<Vec<_>>()
+    /// );
+    /// assert_eq!("", fields["applicability"]);
+    /// assert_eq!("warn", fields["default_level"]);
+    /// ```
     #[must_use]
-    fn internal_lints(lints: &[Self]) -> Vec<Self> {
-        lints.iter().filter(|l| l.group == "internal").cloned().collect()
+    pub fn as_fields(&self) -> BTreeMap<&'static str, String> {
+        BTreeMap::from([
+            ("name", self.name.clone()),
+            ("group", self.group.clone()),
+            ("desc", self.desc.clone()),
+            ("raw_desc", self.raw_desc.clone()),
+            ("module", self.module.clone()),
+            ("declaration_range", format!("{:?}", self.declaration_range)),
+            ("line", self.line.to_string()),
+            ("applicability", self.applicability.clone().unwrap_or_default()),
+            ("default_level", self.default_level.clone().unwrap_or_default()),
+        ])
     }
 
-    /// Returns the lints in a `HashMap`, grouped by the different lint groups
+    /// Returns the link to the lint's documentation
     #[must_use]
-    fn by_lint_group(lints: impl Iterator<Item = Self>) -> HashMap<String, Vec<Self>> {
-        lints.map(|lint| (lint.group.to_string(), lint)).into_group_map()
+    pub fn url(&self) -> String {
+        self.url_with_base(DOCS_LINK)
     }
-}
 
-#[derive(Clone, PartialEq, Eq, Debug)]
-struct DeprecatedLint {
-    name: String,
-    reason: String,
-    declaration_range: Range<usize>,
-}
-impl DeprecatedLint {
-    fn new(name: &str, reason: &str, declaration_range: Range<usize>) -> Self {
-        Self {
-            name: name.to_lowercase(),
-            reason: remove_line_splices(reason),
-            declaration_range,
+    /// Returns the link to the lint's documentation on the lint list at `base`, e.g.
+    /// `https://rust-lang.github.io/rust-clippy/rust-1.72.0/index.html`. Trailing slashes of `base`
+    /// are ignored.
+    #[must_use]
+    pub fn url_with_base(&self, base: &str) -> String {
+        self.url_with(base, UrlScheme::default())
+    }
+
+    /// Returns the link to the lint's documentation on the lint list at `base`, see
+    /// `url_with_base`, using the anchor format of `scheme`
+    #[must_use]
+    pub fn url_with(&self, base: &str, scheme: UrlScheme) -> String {
+        scheme.url(base, &self.name)
+    }
+
+    /// Returns the description without markdown, i.e. with the backticks around inline code and
+    /// the `*` emphasis markers removed. The contents of inline code are kept as is.
+    #[must_use]
+    pub fn plain_desc(&self) -> String {
+        self.desc
+            .split('`')
+            .enumerate()
+            .map(|(i, part)| {
+                if i % 2 == 0 {
+                    Cow::Owned(part.replace('*', ""))
+                } else {
+                    Cow::Borrowed(part)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the description escaped for HTML, with inline code wrapped in `<code>` instead of
+    /// backticks
+    #[must_use]
+    pub fn desc_html(&self) -> String {
+        let mut html = String::with_capacity(self.desc.len());
+        let mut in_code = false;
+        for c in self.desc.chars() {
+            match c {
+                '`' => {
+                    html.push_str(if in_code { "</code>" } else { "<code>" });
+                    in_code = !in_code;
+                },
+                '&' => html.push_str("&amp;"),
+                '<' => html.push_str("&lt;"),
+                '>' => html.push_str("&gt;"),
+                '"' => html.push_str("&quot;"),
+                _ => html.push(c),
+            }
         }
+        if in_code {
+            html.push_str("</code>");
+        }
+        html
     }
-}
 
-struct RenamedLint {
-    old_name: String,
-    new_name: String,
-}
-impl RenamedLint {
-    fn new(old_name: &str, new_name: &str) -> Self {
-        Self {
-            old_name: remove_line_splices(old_name),
-            new_name: remove_line_splices(new_name),
+    /// Returns the names of the other lints mentioned in the description, in order of appearance.
+    /// Only whole words are matched, so `ptr_arg` isn't found in `ptr_args`.
+    ///
+    /// `all` contains the lowercased names of all lints.
+    #[must_use]
+    pub fn referenced_lints(&self, all: &HashSet<String>) -> Vec<String> {
+        let mut referenced = Vec::new();
+        for word in self
+            .desc
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map(str::to_lowercase)
+        {
+            if word != self.name && all.contains(&word) && !referenced.contains(&word) {
+                referenced.push(word);
+            }
         }
+        referenced
     }
-}
 
-/// Generates the `register_removed` code
-#[must_use]
-fn gen_deprecated(lints: &[DeprecatedLint]) -> String {
-    let mut output = GENERATED_FILE_COMMENT.to_string();
-    output.push_str("{\n");
-    for lint in lints {
-        let _: fmt::Result = write!(
-            output,
-            concat!(
-                "    store.register_removed(\n",
-                "        \"clippy::{}\",\n",
-                "        \"{}\",\n",
-                "    );\n"
-            ),
-            lint.name, lint.reason,
-        );
+    /// Returns the category of the lint parsed from its `group`
+    #[must_use]
+    pub fn category(&self) -> LintCategory {
+        LintCategory::from_group(&self.group)
     }
-    output.push_str("}\n");
 
-    output
-}
+    /// Checks if the lint is in one of the internal groups (`internal` or `internal_warn`)
+    #[must_use]
+    pub fn is_internal(&self) -> bool {
+        self.group.starts_with("internal")
+    }
 
-/// Generates the code for registering lints
-#[must_use]
-fn gen_declared_lints<'a>(
-    internal_lints: impl Iterator<Item = &'a Lint>,
-    usable_lints: impl Iterator<Item = &'a Lint>,
-) -> String {
-    let mut details: Vec<_> = internal_lints
-        .map(|l| (false, &l.module, l.name.to_uppercase()))
-        .chain(usable_lints.map(|l| (true, &l.module, l.name.to_uppercase())))
-        .collect();
-    details.sort_unstable();
+    /// Checks if the lint is in the `deprecated` group
+    ///
+    /// Lints declared with `declare_deprecated_lint!` are gathered as [`DeprecatedLint`]s, not as
+    /// `Lint`s, so this only holds for lints placed in that group by hand.
+    #[must_use]
+    pub fn is_deprecated(&self) -> bool {
+        self.group == "deprecated"
+    }
 
-    let mut output = GENERATED_FILE_COMMENT.to_string();
-    output.push_str("pub(crate) static LINTS: &[&crate::LintInfo] = &[\n");
+    /// Checks if the lint can be used outside of Clippy, i.e. it's not an internal lint
+    #[must_use]
+    pub fn is_usable(&self) -> bool {
+        !self.is_internal()
+    }
 
-    for (is_public, module_name, lint_name) in details {
-        if !is_public {
-            output.push_str("    #[cfg(feature = \"internal\")]\n");
-        }
-        let _: fmt::Result = writeln!(output, "    crate::{module_name}::{lint_name}_INFO,");
+    /// Returns all non-deprecated lints and non-internal lints
+    #[must_use]
+    pub fn usable_lints(lints: &[Self]) -> Vec<Self> {
+        lints.iter().filter(|l| l.is_usable()).cloned().collect()
     }
-    output.push_str("];\n");
 
-    output
-}
+    /// Returns the usable lints whose group isn't one of `exclude_groups`, which are compared by
+    /// exact name
+    #[allow(clippy::implicit_hasher)]
+    pub fn usable_lints_excluding<'a>(
+        lints: impl Iterator<Item = Self> + 'a,
+        exclude_groups: &'a HashSet<String>,
+    ) -> impl Iterator<Item = Self> + 'a {
+        lints.filter(move |lint| lint.is_usable() && !exclude_groups.contains(&lint.group))
+    }
 
-fn gen_deprecated_lints_test(lints: &[DeprecatedLint]) -> String {
-    let mut res: String = GENERATED_FILE_COMMENT.into();
-    for lint in lints {
-        writeln!(res, "#![warn(clippy::{})]", lint.name).unwrap();
+    /// Returns all internal lints (not `internal_warn` lints)
+    #[must_use]
+    pub fn internal_lints(lints: &[Self]) -> Vec<Self> {
+        lints.iter().filter(|l| l.group == "internal").cloned().collect()
     }
-    res.push_str("\nfn main() {}\n");
-    res
-}
 
-fn gen_renamed_lints_test(lints: &[RenamedLint]) -> String {
-    let mut seen_lints = HashSet::new();
-    let mut res: String = GENERATED_FILE_COMMENT.into();
-    for lint in lints {
-        if seen_lints.insert(&lint.new_name) {
-            writeln!(res, "#![allow({})]", lint.new_name).unwrap();
-        }
+    /// Returns the lints in any of the internal groups, the complement of `usable_lints`
+    pub fn all_internal_lints(lints: impl Iterator<Item = Self>) -> impl Iterator<Item = Self> {
+        lints.filter(Self::is_internal)
     }
-    seen_lints.clear();
-    for lint in lints {
-        if seen_lints.insert(&lint.old_name) {
-            writeln!(res, "#![warn({})]", lint.old_name).unwrap();
-        }
+
+    /// Returns the lints in a `HashMap`, grouped by the different lint groups
+    #[must_use]
+    pub fn by_lint_group(lints: impl Iterator<Item = Self>) -> HashMap<String, Vec<Self>> {
+        lints.map(|lint| (lint.group.to_string(), lint)).into_group_map()
     }
-    res.push_str("\nfn main() {}\n");
-    res
-}
 
-fn gen_renamed_lints_list(lints: &[RenamedLint]) -> String {
-    const HEADER: &str = "\
-        // This file is managed by `cargo dev rename_lint`. Prefer using that when possible.\n\n\
-        #[rustfmt::skip]\n\
-        pub static RENAMED_LINTS: &[(&str, &str)] = &[\n";
+    /// Returns the lints sorted by their category in the order of `CATEGORY_PRECEDENCE`, then by
+    /// name. Lints of unknown categories come last, sorted by group.
+    #[must_use]
+    pub fn sorted_for_display(lints: &[Self]) -> Vec<Self> {
+        let mut lints = lints.to_vec();
+        lints.sort_by_cached_key(|lint| (lint.category().precedence(), lint.group.clone(), lint.name.clone()));
+        lints
+    }
 
-    let mut res = String::from(HEADER);
-    for lint in lints {
-        writeln!(res, "    (\"{}\", \"{}\"),", lint.old_name, lint.new_name).unwrap();
+    /// Returns the lints grouped by group and then by module. The lints of each module are sorted
+    /// by name.
+    #[must_use]
+    pub fn by_group_then_module(lints: &[Self]) -> BTreeMap<String, BTreeMap<String, Vec<Self>>> {
+        let mut result: BTreeMap<String, BTreeMap<String, Vec<Self>>> = BTreeMap::new();
+        for lint in lints {
+            result
+                .entry(lint.group.clone())
+                .or_default()
+                .entry(lint.module.clone())
+                .or_default()
+                .push(lint.clone());
+        }
+        for lints in result.values_mut().flat_map(BTreeMap::values_mut) {
+            lints.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        result
     }
-    res.push_str("];\n");
-    res
-}
 
-/// Gathers all lints defined in `clippy_lints/src`
-fn gather_all() -> (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>) {
-    let mut lints = Vec::with_capacity(1000);
-    let mut deprecated_lints = Vec::with_capacity(50);
-    let mut renamed_lints = Vec::with_capacity(50);
-
-    for (rel_path, file) in clippy_lints_src_files() {
-        let path = file.path();
-        let contents =
-            fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
-        let module = rel_path
-            .components()
-            .map(|c| c.as_os_str().to_str().unwrap())
-            .collect::<Vec<_>>()
-            .join("::");
-
-        // If the lints are stored in mod.rs, we get the module name from
-        // the containing directory:
-        let module = if let Some(module) = module.strip_suffix("::mod.rs") {
-            module
-        } else {
-            module.strip_suffix(".rs").unwrap_or(&module)
-        };
+    /// Returns the distinct categories of the lints in the order of `CATEGORY_PRECEDENCE`. Unknown
+    /// categories come last, sorted by group.
+    #[must_use]
+    pub fn present_categories(lints: &[Self]) -> Vec<LintCategory> {
+        let categories: HashSet<_> = lints.iter().map(Self::category).collect();
+        let mut categories: Vec<_> = categories.into_iter().collect();
+        categories.sort_by_cached_key(|c| (c.precedence(), c.as_str().to_string()));
+        categories
+    }
 
-        match module {
-            "deprecated_lints" => parse_deprecated_contents(&contents, &mut deprecated_lints),
-            "renamed_lints" => parse_renamed_contents(&contents, &mut renamed_lints),
-            _ => parse_contents(&contents, module, &mut lints),
+    /// Returns the lints in a `BTreeMap`, grouped by the different lint groups. The lints of each
+    /// group are sorted by name.
+    #[must_use]
+    pub fn by_lint_group_sorted(lints: &[Self]) -> BTreeMap<String, Vec<Self>> {
+        let mut groups: BTreeMap<String, Vec<Self>> = BTreeMap::new();
+        for lint in lints {
+            groups.entry(lint.group.clone()).or_default().push(lint.clone());
         }
+        for lints in groups.values_mut() {
+            lints.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        groups
     }
-    (lints, deprecated_lints, renamed_lints)
-}
 
-fn clippy_lints_src_files() -> impl Iterator<Item = (PathBuf, DirEntry)> {
-    let root_path = clippy_project_root().join("clippy_lints/src");
-    let iter = WalkDir::new(&root_path).into_iter();
-    iter.map(Result::unwrap)
-        .filter(|f| f.path().extension() == Some(OsStr::new("rs")))
-        .map(move |f| (f.path().strip_prefix(&root_path).unwrap().to_path_buf(), f))
-}
+    /// Returns the lints in a `HashMap`, grouped by the different lint groups. Only the first lint
+    /// with a given name is kept in each group, and the lints of each group are sorted by name.
+    #[must_use]
+    pub fn by_lint_group_dedup(lints: &[Self]) -> HashMap<String, Vec<Self>> {
+        let mut groups = Self::by_lint_group(lints.iter().cloned());
+        for lints in groups.values_mut() {
+            let mut seen = HashSet::new();
+            lints.retain(|lint| seen.insert(lint.name.clone()));
+            lints.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        groups
+    }
 
-macro_rules! match_tokens {
-    ($iter:ident, $($token:ident $({$($fields:tt)*})? $(($capture:ident))?)*) => {
-         {
-            $(#[allow(clippy::redundant_pattern)] let Some(LintDeclSearchResult {
-                    token_kind: TokenKind::$token $({$($fields)*})?,
-                    content: $($capture @)? _,
-                    ..
-            }) = $iter.next() else {
-                continue;
-            };)*
-            #[allow(clippy::unused_unit)]
-            { ($($($capture,)?)*) }
+    /// Returns the usable lints grouped by the uppercased first letter of their name, with the
+    /// lints of each letter sorted by name. Names which don't start with a letter are put under
+    /// `'#'`.
+    #[must_use]
+    pub fn by_first_letter(lints: &[Self]) -> BTreeMap<char, Vec<Self>> {
+        let mut letters: BTreeMap<char, Vec<Self>> = BTreeMap::new();
+        for lint in lints.iter().filter(|l| l.is_usable()) {
+            let letter = match lint.name.chars().next() {
+                Some(c) if c.is_ascii_alphabetic() => c.to_ascii_uppercase(),
+                _ => '#',
+            };
+            letters.entry(letter).or_default().push(lint.clone());
+        }
+        for lints in letters.values_mut() {
+            lints.sort_by(|a, b| a.name.cmp(&b.name));
         }
+        letters
     }
-}
 
-pub(crate) use match_tokens;
+    /// Returns the number of usable lints in each lint group
+    #[must_use]
+    pub fn group_counts(lints: &[Self]) -> BTreeMap<String, usize> {
+        Self::by_lint_group(Self::usable_lints(lints).into_iter())
+            .into_iter()
+            .map(|(group, lints)| (group, lints.len()))
+            .collect()
+    }
 
-pub(crate) struct LintDeclSearchResult<'a> {
-    pub token_kind: TokenKind,
-    pub content: &'a str,
-    pub range: Range<usize>,
-}
+    /// Returns the names of all modules declaring lints. Deprecated lints are declared in the
+    /// `deprecated_lints` module.
+    #[must_use]
+    pub fn modules(lints: &[Self], deprecated_lints: &[DeprecatedLint]) -> BTreeSet<String> {
+        Self::module_counts(lints, deprecated_lints).into_keys().collect()
+    }
 
-/// Parse a source file looking for `declare_clippy_lint` macro invocations.
-fn parse_contents(contents: &str, module: &str, lints: &mut Vec<Lint>) {
-    let mut offset = 0usize;
-    let mut iter = tokenize(contents).map(|t| {
-        let range = offset..offset + t.len as usize;
-        offset = range.end;
+    /// Returns the number of lints declared in each module, including deprecated lints
+    #[must_use]
+    pub fn module_counts(lints: &[Self], deprecated_lints: &[DeprecatedLint]) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for lint in lints {
+            *counts.entry(lint.module.clone()).or_default() += 1;
+        }
+        if !deprecated_lints.is_empty() {
+            counts.insert("deprecated_lints".to_string(), deprecated_lints.len());
+        }
+        counts
+    }
 
-        LintDeclSearchResult {
-            token_kind: t.kind,
-            content: &contents[range.clone()],
-            range,
+    /// Returns the number of usable lints whose description mentions each word. Words are split on
+    /// non-alphanumeric characters and lowercased. No stopwords are removed.
+    #[must_use]
+    pub fn description_word_frequencies(lints: &[Self]) -> BTreeMap<String, usize> {
+        let mut frequencies = BTreeMap::new();
+        for lint in lints.iter().filter(|l| l.is_usable()) {
+            let words: BTreeSet<_> = lint
+                .desc
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|w| !w.is_empty())
+                .map(str::to_lowercase)
+                .collect();
+            for word in words {
+                *frequencies.entry(word).or_default() += 1;
+            }
         }
-    });
+        frequencies
+    }
 
-    while let Some(LintDeclSearchResult { range, .. }) = iter.find(
-        |LintDeclSearchResult {
-             token_kind, content, ..
-         }| token_kind == &TokenKind::Ident && *content == "declare_clippy_lint",
-    ) {
-        let start = range.start;
-        let mut iter = iter
-            .by_ref()
-            .filter(|t| !matches!(t.token_kind, TokenKind::Whitespace | TokenKind::LineComment { .. }));
-        // matches `!{`
-        match_tokens!(iter, Bang OpenBrace);
-        match iter.next() {
-            // #[clippy::version = "version"] pub
-            Some(LintDeclSearchResult {
-                token_kind: TokenKind::Pound,
-                ..
-            }) => {
-                match_tokens!(iter, OpenBracket Ident Colon Colon Ident Eq Literal{..} CloseBracket Ident);
-            },
-            // pub
-            Some(LintDeclSearchResult {
-                token_kind: TokenKind::Ident,
-                ..
-            }) => (),
-            _ => continue,
+    /// Computes the numbers of `LintStats` in a single pass over the lints
+    #[must_use]
+    pub fn compute_stats(lints: &[Self], deprecated_lints: &[DeprecatedLint]) -> LintStats {
+        let mut stats = LintStats {
+            total: lints.len(),
+            deprecated: deprecated_lints.len(),
+            ..LintStats::default()
+        };
+        let mut modules = HashSet::new();
+        for lint in lints {
+            if lint.is_usable() {
+                stats.usable += 1;
+                *stats.group_counts.entry(lint.group.clone()).or_default() += 1;
+            } else {
+                stats.internal += 1;
+            }
+            modules.insert(&*lint.module);
         }
+        if !deprecated_lints.is_empty() {
+            modules.insert("deprecated_lints");
+        }
+        stats.module_count = modules.len();
+        stats
+    }
 
-        let (name, group, desc) = match_tokens!(
-            iter,
-            // LINT_NAME
-            Ident(name) Comma
-            // group,
-            Ident(group) Comma
-            // "description"
-            Literal{..}(desc)
-        );
+    /// Returns the number of usable lints
+    #[must_use]
+    pub fn total_usable(lints: &[Self]) -> usize {
+        Self::usable_lints(lints).len()
+    }
 
-        if let Some(LintDeclSearchResult {
-            token_kind: TokenKind::CloseBrace,
-            range,
-            ..
-        }) = iter.next()
-        {
-            lints.push(Lint::new(name, group, desc, module, start..range.end));
-        }
+    /// Returns all lints which aren't registered in any lint pass
+    ///
+    /// `registered` contains the lowercased lint names as collected by `parse_lint_pass_contents`.
+    #[must_use]
+    pub fn unregistered_lints(lints: &[Self], registered: &HashSet<String>) -> Vec<Self> {
+        lints
+            .iter()
+            .filter(|l| !registered.contains(&l.name))
+            .cloned()
+            .collect()
     }
-}
 
-/// Parse a source file looking for `declare_deprecated_lint` macro invocations.
-fn parse_deprecated_contents(contents: &str, lints: &mut Vec<DeprecatedLint>) {
-    let mut offset = 0usize;
-    let mut iter = tokenize(contents).map(|t| {
-        let range = offset..offset + t.len as usize;
-        offset = range.end;
+    /// Returns the lints whose name isn't in `known`, e.g. the lints added since `known` was
+    /// collected. Lint names are lowercase, so `known` should contain lowercased names.
+    pub fn new_since<'a>(lints: &'a [Self], known: &'a HashSet<String>) -> impl Iterator<Item = &'a Self> {
+        lints.iter().filter(|l| !known.contains(&l.name))
+    }
 
-        LintDeclSearchResult {
-            token_kind: t.kind,
-            content: &contents[range.clone()],
-            range,
+    /// Returns all lints which aren't listed in `declared_lints.rs`. Lint groups are built from the
+    /// declared lints, so these lints won't be part of their group. `internal_warn` lints aren't
+    /// part of any group and are ignored.
+    ///
+    /// `registered` contains the lowercased lint names as collected by
+    /// `parse_declared_lints_contents`.
+    #[must_use]
+    pub fn lints_missing_group_registration<'a>(lints: &'a [Self], registered: &HashSet<String>) -> Vec<&'a Self> {
+        lints
+            .iter()
+            .filter(|l| l.group != "internal_warn" && !registered.contains(&l.name))
+            .collect()
+    }
+
+    /// Returns all lints whose group isn't a known lint category, sorted by name
+    #[must_use]
+    pub fn lints_with_unknown_group(lints: &[Self]) -> Vec<&Self> {
+        let mut unknown: Vec<_> = lints
+            .iter()
+            .filter(|l| matches!(l.category(), LintCategory::Unknown(_)))
+            .collect();
+        unknown.sort_by(|a, b| a.name.cmp(&b.name));
+        unknown
+    }
+
+    /// Returns the `n` usable lints with the longest descriptions, longest first, and the `n` with
+    /// the shortest descriptions, shortest first. Lengths are counted in characters and ties are
+    /// ordered by name.
+    #[must_use]
+    pub fn description_length_extremes(lints: &[Self], n: usize) -> (Vec<&Self>, Vec<&Self>) {
+        let mut usable: Vec<_> = lints.iter().filter(|l| l.is_usable()).collect();
+        usable.sort_by_cached_key(|l| (std::cmp::Reverse(l.desc.chars().count()), &l.name));
+        let longest = usable.iter().take(n).copied().collect();
+        usable.sort_by_cached_key(|l| (l.desc.chars().count(), &l.name));
+        usable.truncate(n);
+        (longest, usable)
+    }
+
+    /// Returns the lints whose name is also the name of a rustc lint, ignoring case, sorted by
+    /// name. Such lints are confusing to allow or deny.
+    #[must_use]
+    pub fn collisions_with_rustc<'a>(lints: &'a [Self], rustc_names: &HashSet<String>) -> Vec<&'a Self> {
+        let rustc_names: HashSet<_> = rustc_names.iter().map(|name| name.to_lowercase()).collect();
+        let mut collisions: Vec<_> = lints.iter().filter(|l| rustc_names.contains(&l.name)).collect();
+        collisions.sort_by(|a, b| a.name.cmp(&b.name));
+        collisions
+    }
+
+    /// Returns the lints which are inconsistent with the deprecated lints, sorted by name. These
+    /// are lints declared in the `deprecated` group, which only `declare_deprecated_lint!`
+    /// should use, and lints whose name is also in `deprecated_lints`.
+    #[must_use]
+    pub fn inconsistent_deprecation<'a>(lints: &'a [Self], deprecated_lints: &[DeprecatedLint]) -> Vec<&'a Self> {
+        let deprecated: HashSet<_> = deprecated_lints.iter().map(|l| &*l.name).collect();
+        let mut inconsistent: Vec<_> = lints
+            .iter()
+            .filter(|l| l.is_deprecated() || deprecated.contains(&*l.name))
+            .collect();
+        inconsistent.sort_by(|a, b| a.name.cmp(&b.name));
+        inconsistent
+    }
+
+    /// Returns the lints whose name or description contains `query`, ignoring case. Lints matching
+    /// by name come first, followed by the ones only matching by description, each sorted by name.
+    #[must_use]
+    pub fn search<'a>(lints: &'a [Self], query: &str) -> Vec<&'a Self> {
+        let query = query.to_lowercase();
+        let (mut by_name, mut by_desc): (Vec<_>, Vec<_>) = lints
+            .iter()
+            .filter(|l| l.name.to_lowercase().contains(&query) || l.desc.to_lowercase().contains(&query))
+            .partition(|l| l.name.to_lowercase().contains(&query));
+        by_name.sort_by(|a, b| a.name.cmp(&b.name));
+        by_desc.sort_by(|a, b| a.name.cmp(&b.name));
+        by_name.append(&mut by_desc);
+        by_name
+    }
+
+    /// Returns all lints whose name isn't lowercase snake case, i.e. matching `^[a-z][a-z0-9_]*$`,
+    /// or is longer than `MAX_LINT_NAME_LEN`
+    #[must_use]
+    pub fn invalid_lint_names(lints: &[Self]) -> Vec<&Self> {
+        lints
+            .iter()
+            .filter(|l| {
+                !l.name.starts_with(|c: char| c.is_ascii_lowercase())
+                    || !l
+                        .name
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                    || l.name.len() > MAX_LINT_NAME_LEN
+            })
+            .collect()
+    }
+
+    /// Returns all lints whose description is empty or only contains whitespace
+    #[must_use]
+    pub fn lints_with_empty_desc(lints: &[Self]) -> Vec<&Self> {
+        lints.iter().filter(|l| l.desc.trim().is_empty()).collect()
+    }
+
+    /// Returns all lints whose description only restates the name, ignoring case, whitespace and
+    /// whether words are separated by underscores or spaces
+    #[must_use]
+    pub fn redundant_descriptions(lints: &[Self]) -> Vec<&Self> {
+        fn words(s: &str) -> Vec<String> {
+            s.split(|c: char| c == '_' || c.is_whitespace())
+                .filter(|w| !w.is_empty())
+                .map(str::to_lowercase)
+                .collect()
         }
-    });
 
-    while let Some(LintDeclSearchResult { range, .. }) = iter.find(
-        |LintDeclSearchResult {
-             token_kind, content, ..
-         }| token_kind == &TokenKind::Ident && *content == "declare_deprecated_lint",
-    ) {
-        let start = range.start;
+        lints.iter().filter(|l| words(&l.name) == words(&l.desc)).collect()
+    }
 
-        let mut iter = iter.by_ref().filter(|LintDeclSearchResult { ref token_kind, .. }| {
-            !matches!(token_kind, TokenKind::Whitespace | TokenKind::LineComment { .. })
-        });
-        let (name, reason) = match_tokens!(
-            iter,
-            // !{
-            Bang OpenBrace
-            // #[clippy::version = "version"]
-            Pound OpenBracket Ident Colon Colon Ident Eq Literal{..} CloseBracket
-            // pub LINT_NAME,
-            Ident Ident(name) Comma
-            // "description"
-            Literal{kind: LiteralKind::Str{..},..}(reason)
-        );
+    /// Returns all lints whose description doesn't follow the style convention of starting in
+    /// lowercase and not ending with a period, along with the reason why
+    #[must_use]
+    pub fn lint_desc_style_violations(lints: &[Self]) -> Vec<(&Self, String)> {
+        let mut violations = Vec::new();
+        for lint in lints {
+            if lint.desc.starts_with(char::is_uppercase) {
+                violations.push((lint, "description starts with an uppercase letter".to_string()));
+            }
+            if lint.desc.trim_end().ends_with('.') {
+                violations.push((lint, "description ends with a period".to_string()));
+            }
+        }
+        violations
+    }
 
-        if let Some(LintDeclSearchResult {
-            token_kind: TokenKind::CloseBrace,
-            range,
-            ..
-        }) = iter.next()
-        {
-            lints.push(DeprecatedLint::new(name, reason, start..range.end));
+    /// Returns all lints declared in a different module than the one given for them in `expected`
+    ///
+    /// `expected` maps lint names to module names. Lints missing from it are ignored.
+    #[must_use]
+    #[allow(clippy::implicit_hasher)]
+    pub fn lints_in_unexpected_module<'a>(lints: &'a [Self], expected: &HashMap<String, String>) -> Vec<&'a Self> {
+        lints
+            .iter()
+            .filter(|l| expected.get(&l.name).is_some_and(|module| *module != l.module))
+            .collect()
+    }
+
+    /// Returns the name of every lint declared more than once, along with the modules declaring it
+    #[must_use]
+    pub fn find_duplicates(lints: &[Self]) -> Vec<(String, Vec<String>)> {
+        lints
+            .iter()
+            .map(|lint| (lint.name.clone(), lint.module.clone()))
+            .into_group_map()
+            .into_iter()
+            .filter(|(_, modules)| modules.len() > 1)
+            .sorted()
+            .collect()
+    }
+
+    /// Returns the `restriction` lints whose description has fewer than `min_words` words, sorted
+    /// by name. Restriction lints are opt-in, so their descriptions should explain when to
+    /// enable them.
+    #[must_use]
+    pub fn terse_restriction_lints(lints: &[Self], min_words: usize) -> Vec<&Self> {
+        let mut terse: Vec<_> = lints
+            .iter()
+            .filter(|l| l.category() == LintCategory::Restriction && l.desc.split_whitespace().count() < min_words)
+            .collect();
+        terse.sort_by(|a, b| a.name.cmp(&b.name));
+        terse
+    }
+
+    /// Returns the lints whose description contains a tab, starts or ends with whitespace or has
+    /// two or more whitespace characters in a row
+    #[must_use]
+    pub fn whitespace_dirty_descriptions(lints: &[Self]) -> Vec<&Self> {
+        lints
+            .iter()
+            .filter(|l| {
+                let desc = &l.desc;
+                desc.contains('\t')
+                    || desc.trim() != desc
+                    || desc
+                        .chars()
+                        .zip(desc.chars().skip(1))
+                        .any(|(a, b)| a.is_whitespace() && b.is_whitespace())
+            })
+            .collect()
+    }
+
+    /// Returns the anchors of the links returned by `url` which are shared by more than one lint,
+    /// sorted
+    #[must_use]
+    pub fn duplicate_anchors(lints: &[Self]) -> Vec<String> {
+        lints
+            .iter()
+            .filter_map(|l| l.url().rsplit_once('#').map(|(_, anchor)| anchor.to_string()))
+            .counts()
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .map(|(anchor, _)| anchor)
+            .sorted()
+            .collect()
+    }
+
+    /// Checks that no two lints have the same name, ignoring case
+    ///
+    /// # Errors
+    ///
+    /// Returns every lowercased name used more than once, sorted
+    pub fn assert_unique_names(lints: &[Self]) -> Result<(), Vec<String>> {
+        let mut seen = HashSet::new();
+        let duplicates: BTreeSet<_> = lints
+            .iter()
+            .map(|l| l.name.to_lowercase())
+            .filter(|name| !seen.insert(name.clone()))
+            .collect();
+        if duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(duplicates.into_iter().collect())
+        }
+    }
+
+    /// Returns the names of the pairs of usable lints whose descriptions have a similarity of at
+    /// least `threshold`, sorted by name. The similarity ranges from `0.0` to `1.0` and is the
+    /// Levenshtein distance of the lowercased descriptions normalized by the length of the longer
+    /// one, subtracted from `1.0`.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn near_duplicate_descriptions(lints: &[Self], threshold: f64) -> Vec<(String, String)> {
+        let lints: Vec<_> = Self::usable_lints(lints)
+            .into_iter()
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(|l| (l.name, l.desc.to_lowercase().chars().collect::<Vec<_>>()))
+            .collect();
+        let mut pairs = Vec::new();
+        for (i, (name, desc)) in lints.iter().enumerate() {
+            for (other_name, other_desc) in &lints[i + 1..] {
+                let len = desc.len().max(other_desc.len());
+                let similarity = if len == 0 {
+                    1.0
+                } else {
+                    1.0 - levenshtein(desc, other_desc) as f64 / len as f64
+                };
+                if similarity >= threshold {
+                    pairs.push((name.clone(), other_name.clone()));
+                }
+            }
         }
+        pairs
     }
 }
 
-fn parse_renamed_contents(contents: &str, lints: &mut Vec<RenamedLint>) {
-    for line in contents.lines() {
-        let mut offset = 0usize;
-        let mut iter = tokenize(line).map(|t| {
-            let range = offset..offset + t.len as usize;
-            offset = range.end;
+/// Computes the Levenshtein distance between two strings
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev + usize::from(ca != cb);
+            prev = row[j + 1];
+            row[j + 1] = substitution.min(prev + 1).min(row[j] + 1);
+        }
+    }
+    row[b.len()]
+}
 
-            LintDeclSearchResult {
-                token_kind: t.kind,
-                content: &line[range.clone()],
-                range,
-            }
-        });
+/// Lints are ordered by module, then by name. The remaining fields only break ties between lints
+/// declared more than once, keeping the order consistent with `Eq`.
+impl Ord for Lint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Destructured so a new field can't be left out of the comparison
+        let Self {
+            name,
+            group,
+            desc,
+            raw_desc,
+            module,
+            declaration_range,
+            line,
+            applicability,
+            default_level,
+        } = self;
+        (
+            module,
+            name,
+            group,
+            desc,
+            raw_desc,
+            (declaration_range.start, declaration_range.end),
+            line,
+            applicability,
+            default_level,
+        )
+            .cmp(&(
+                &other.module,
+                &other.name,
+                &other.group,
+                &other.desc,
+                &other.raw_desc,
+                (other.declaration_range.start, other.declaration_range.end),
+                &other.line,
+                &other.applicability,
+                &other.default_level,
+            ))
+    }
+}
 
-        let (old_name, new_name) = match_tokens!(
-            iter,
-            // ("old_name",
-            Whitespace OpenParen Literal{kind: LiteralKind::Str{..},..}(old_name) Comma
-            // "new_name"),
-            Whitespace Literal{kind: LiteralKind::Str{..},..}(new_name) CloseParen Comma
-        );
-        lints.push(RenamedLint::new(old_name, new_name));
+impl PartialOrd for Lint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-/// Removes the line splices and surrounding quotes from a string literal
-fn remove_line_splices(s: &str) -> String {
-    let s = s
-        .strip_prefix('r')
-        .unwrap_or(s)
-        .trim_matches('#')
-        .strip_prefix('"')
-        .and_then(|s| s.strip_suffix('"'))
-        .unwrap_or_else(|| panic!("expected quoted string, found `{s}`"));
-    let mut res = String::with_capacity(s.len());
-    unescape::unescape_literal(s, unescape::Mode::Str, &mut |range, ch| {
-        if ch.is_ok() {
-            res.push_str(&s[range]);
+// The derives above use `remote = "Self"`, so they only generate the inherent `Lint::serialize`
+// and `Lint::deserialize`. The trait impls wrap them to add the `id` field in front of the fields.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Lint {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(serde::Serialize)]
+        struct WithId<'a> {
+            id: String,
+            #[serde(flatten, with = "Lint")]
+            lint: &'a Lint,
         }
-    });
-    res
+
+        serde::Serialize::serialize(
+            &WithId {
+                id: self.id(),
+                lint: self,
+            },
+            serializer,
+        )
+    }
 }
 
-/// Replaces a region in a file delimited by two lines matching regexes.
-///
-/// `path` is the relative path to the file on which you want to perform the replacement.
-///
-/// See `replace_region_in_text` for documentation of the other options.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Lint {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Lint::deserialize(deserializer)
+    }
+}
+
+/// The format of the anchors linking to a lint on the lint list.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum UrlScheme {
+    /// `#lint_name`, used by the current lint list
+    #[default]
+    Name,
+    /// `#clippy::lint_name`
+    ToolPrefixed,
+}
+
+impl UrlScheme {
+    /// Returns the anchor of the lint called `name`, without the `#`
+    #[must_use]
+    pub fn anchor(self, name: &str) -> String {
+        match self {
+            Self::Name => name.into(),
+            Self::ToolPrefixed => format!("clippy::{name}"),
+        }
+    }
+
+    /// Returns the link to the lint called `name` on the lint list at `base`. Trailing slashes of
+    /// `base` are ignored.
+    #[must_use]
+    pub fn url(self, base: &str, name: &str) -> String {
+        format!("{}#{}", base.trim_end_matches('/'), self.anchor(name))
+    }
+}
+
+/// Formats the lint on a single line as `name (group): desc`.
 ///
-/// # Panics
+/// ```
+/// use clippy_dev::update_lints::gather_from_sources;
 ///
-/// Panics if the path could not read or then written
-fn replace_region_in_file(
-    update_mode: UpdateMode,
-    path: &Path,
-    start: &str,
-    end: &str,
-    write_replacement: impl FnMut(&mut String),
-) {
-    let contents = fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
-    let new_contents = match replace_region_in_text(&contents, start, end, write_replacement) {
-        Ok(x) => x,
-        Err(delim) => panic!("Couldn't find `{delim}` in file `{}`", path.display()),
-    };
-
-    match update_mode {
-        UpdateMode::Check if contents != new_contents => exit_with_failure(),
-        UpdateMode::Check => (),
-        UpdateMode::Change => {
-            if let Err(e) = fs::write(path, new_contents.as_bytes()) {
-                panic!("Cannot write to `{}`: {e}", path.display());
-            }
-        },
+/// let lints = gather_from_sources([("ptr", r#"declare_clippy_lint! { pub PTR_ARG, style, "desc" }"#)]);
+/// assert_eq!("ptr_arg (style): desc", lints[0].to_string());
+/// ```
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.name, self.group, self.desc)
     }
 }
 
-/// Replaces a region in a text delimited by two strings. Returns the new text if both delimiters
-/// were found, or the missing delimiter if not.
-fn replace_region_in_text<'a>(
-    text: &str,
-    start: &'a str,
-    end: &'a str,
-    mut write_replacement: impl FnMut(&mut String),
-) -> Result<String, &'a str> {
-    let (text_start, rest) = text.split_once(start).ok_or(start)?;
-    let (_, text_end) = rest.split_once(end).ok_or(end)?;
+/// Builds a `Lint` field by field, see `Lint::builder`.
+#[derive(Clone, Default, Debug)]
+pub struct LintBuilder {
+    name: String,
+    group: String,
+    desc: String,
+    module: String,
+    declaration_range: Range<usize>,
+}
 
-    let mut res = String::with_capacity(text.len() + 4096);
-    res.push_str(text_start);
-    res.push_str(start);
-    write_replacement(&mut res);
-    res.push_str(end);
-    res.push_str(text_end);
+impl LintBuilder {
+    /// Sets the lint's name, which is lowercased like in the declaration
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = name.into();
+        self
+    }
 
-    Ok(res)
-}
+    /// Sets the lint's group, which is lowercased like in the declaration
+    #[must_use]
+    pub fn group(mut self, group: &str) -> Self {
+        self.group = group.into();
+        self
+    }
 
-fn try_rename_file(old_name: &Path, new_name: &Path) -> bool {
+    /// Sets the contents of the description's string literal, without the quotes. Line splices are
+    /// removed like in the declaration.
+    #[must_use]
+    pub fn desc(mut self, desc: &str) -> Self {
+        self.desc = desc.into();
+        self
+    }
+
+    /// Sets the module declaring the lint, e.g. `methods::map_flatten`
+    #[must_use]
+    pub fn module(mut self, module: &str) -> Self {
+        self.module = module.into();
+        self
+    }
+
+    /// Sets the byte range of the declaration in the module's file, which defaults to `0..0`
+    #[must_use]
+    pub fn declaration_range(mut self, declaration_range: Range<usize>) -> Self {
+        self.declaration_range = declaration_range;
+        self
+    }
+
+    /// Builds the lint the same way `Lint::new` does, so it compares equal to a parsed lint
+    #[must_use]
+    pub fn build(self) -> Lint {
+        Lint::new(
+            &self.name,
+            &self.group,
+            &format!("\"{}\"", self.desc),
+            &self.module,
+            self.declaration_range,
+        )
+    }
+}
+
+/// An owned list of lints which can be looked up by name.
+#[derive(Clone, Default, Debug)]
+pub struct LintSet {
+    lints: Vec<Lint>,
+    /// The index of each lint in `lints` by name
+    index: HashMap<String, usize>,
+}
+
+impl LintSet {
+    #[must_use]
+    pub fn new(lints: Vec<Lint>) -> Self {
+        let mut set = Self {
+            lints,
+            index: HashMap::new(),
+        };
+        set.rebuild_index();
+        set
+    }
+
+    /// Rebuilds the name index. If a name is declared more than once the first lint is used.
+    fn rebuild_index(&mut self) {
+        self.index.clear();
+        for (i, lint) in self.lints.iter().enumerate() {
+            self.index.entry(lint.name.clone()).or_insert(i);
+        }
+    }
+
+    /// Returns the lint with the given lowercase name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Lint> {
+        self.index.get(name).map(|&i| &self.lints[i])
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lints.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lints.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Lint> {
+        self.lints.iter()
+    }
+
+    /// Returns the lints in the given group
+    ///
+    /// ```
+    /// use clippy_dev::update_lints::{Lint, LintSet};
+    ///
+    /// let set: LintSet = [("ptr_arg", "style"), ("doc_markdown", "pedantic")]
+    ///     .into_iter()
+    ///     .map(|(name, group)| Lint::builder().name(name).group(group).desc("desc").module("module").build())
+    ///     .collect();
+    /// let pedantic: Vec<_> = set.in_group("pedantic").map(|l| l.name.as_str()).collect();
+    /// assert_eq!(vec!["doc_markdown"], pedantic);
+    /// ```
+    pub fn in_group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a Lint> {
+        self.lints.iter().filter(move |l| l.group == group)
+    }
+
+    /// Returns the lints which can be used outside of Clippy, see `Lint::is_usable`
+    pub fn usable(&self) -> impl Iterator<Item = &Lint> {
+        self.lints.iter().filter(|l| l.is_usable())
+    }
+
+    /// Returns the lints grouped by the different lint groups
+    #[must_use]
+    pub fn by_group(&self) -> HashMap<&str, Vec<&Lint>> {
+        self.lints.iter().map(|lint| (&*lint.group, lint)).into_group_map()
+    }
+
+    /// Parses the file at `rel_path` under the lint source root `root` again, replacing the lints
+    /// previously gathered from its module with the ones found now. A file which no longer exists
+    /// has its lints removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but could not be read from
+    #[cfg(feature = "fs")]
+    pub fn update_from_file(&mut self, root: impl AsRef<Path>, rel_path: impl AsRef<Path>) -> Result<(), GatherError> {
+        let rel_path = rel_path.as_ref();
+        let path = root.as_ref().join(rel_path);
+        let mut gathered = GatheredLints::default();
+        if path.exists() {
+            gather_from_file(rel_path, &path, &mut gathered)?;
+        }
+
+        let module = module_name(rel_path);
+        self.lints.retain(|lint| lint.module != module);
+        self.lints.append(&mut gathered.0);
+        self.rebuild_index();
+        Ok(())
+    }
+}
+
+impl<T: LintSource> From<&T> for LintSet {
+    fn from(source: &T) -> Self {
+        Self::new(source.lints())
+    }
+}
+
+impl FromIterator<Lint> for LintSet {
+    fn from_iter<T: IntoIterator<Item = Lint>>(iter: T) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+/// Aggregate numbers about a lint list, as computed by `Lint::compute_stats`.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct LintStats {
+    /// The number of lints, not including deprecated lints
+    pub total: usize,
+    pub usable: usize,
+    pub deprecated: usize,
+    pub internal: usize,
+    /// The number of usable lints in each lint group
+    pub group_counts: BTreeMap<String, usize>,
+    /// The number of modules declaring lints, including `deprecated_lints`
+    pub module_count: usize,
+}
+
+/// The differences between two lint lists, as computed by `diff_lints`.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct LintDiff {
+    /// Lints only found in the new list
+    pub added: Vec<Lint>,
+    /// Lints only found in the old list
+    pub removed: Vec<Lint>,
+    /// Lints whose group or description changed, as `(old, new)` pairs
+    pub changed: Vec<(Lint, Lint)>,
+    /// Lints which were only moved to a different module, as `(old, new)` pairs. Only filled in
+    /// with `DiffOptions::ignore_module`.
+    pub moved: Vec<(Lint, Lint)>,
+}
+
+/// Options for `diff_lints_with`.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct DiffOptions {
+    /// Don't count a different module as a change, and report lints whose module is the only
+    /// difference in `moved` instead
+    pub ignore_module: bool,
+}
+
+/// Compares two lint lists, matching lints by name. The results are sorted by name.
+#[must_use]
+pub fn diff_lints(old: &[Lint], new: &[Lint]) -> LintDiff {
+    diff_lints_with(old, new, DiffOptions::default())
+}
+
+/// Compares two lint lists like `diff_lints`, using `options`. Each lint is in at most one of the
+/// lists, so a lint whose group or description changed as well as its module is only in `changed`.
+#[must_use]
+pub fn diff_lints_with(old: &[Lint], new: &[Lint], options: DiffOptions) -> LintDiff {
+    let old_by_name: HashMap<_, _> = old.iter().map(|l| (&*l.name, l)).collect();
+    let new_by_name: HashMap<_, _> = new.iter().map(|l| (&*l.name, l)).collect();
+
+    let mut diff = LintDiff::default();
+    for lint in new {
+        let Some(&old) = old_by_name.get(&*lint.name) else {
+            diff.added.push(lint.clone());
+            continue;
+        };
+        if old.group != lint.group || old.desc != lint.desc {
+            diff.changed.push((old.clone(), lint.clone()));
+        } else if options.ignore_module && old.module != lint.module {
+            diff.moved.push((old.clone(), lint.clone()));
+        }
+    }
+    diff.removed = old
+        .iter()
+        .filter(|l| !new_by_name.contains_key(&*l.name))
+        .cloned()
+        .collect();
+
+    diff.added.sort_by(|a, b| a.name.cmp(&b.name));
+    diff.removed.sort_by(|a, b| a.name.cmp(&b.name));
+    diff.changed.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    diff.moved.sort_by(|(a, _), (b, _)| a.name.cmp(&b.name));
+    diff
+}
+
+/// Counts the lints added in `diff` per module
+#[must_use]
+pub fn additions_by_module(diff: &LintDiff) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for lint in &diff.added {
+        *counts.entry(lint.module.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// The categories a lint can be declared in.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LintCategory {
+    Correctness,
+    Suspicious,
+    Style,
+    Complexity,
+    Perf,
+    Pedantic,
+    Restriction,
+    Nursery,
+    Cargo,
+    Internal,
+    InternalWarn,
+    /// A group which isn't a known category, most likely a typo.
+    Unknown(String),
+}
+
+impl LintCategory {
+    /// Parses the category from the group name used in `declare_clippy_lint!`
+    #[must_use]
+    pub fn from_group(group: &str) -> Self {
+        match group {
+            "correctness" => Self::Correctness,
+            "suspicious" => Self::Suspicious,
+            "style" => Self::Style,
+            "complexity" => Self::Complexity,
+            "perf" => Self::Perf,
+            "pedantic" => Self::Pedantic,
+            "restriction" => Self::Restriction,
+            "nursery" => Self::Nursery,
+            "cargo" => Self::Cargo,
+            "internal" => Self::Internal,
+            "internal_warn" => Self::InternalWarn,
+            _ => Self::Unknown(group.into()),
+        }
+    }
+
+    /// Returns the group name as used in `declare_clippy_lint!`
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Correctness => "correctness",
+            Self::Suspicious => "suspicious",
+            Self::Style => "style",
+            Self::Complexity => "complexity",
+            Self::Perf => "perf",
+            Self::Pedantic => "pedantic",
+            Self::Restriction => "restriction",
+            Self::Nursery => "nursery",
+            Self::Cargo => "cargo",
+            Self::Internal => "internal",
+            Self::InternalWarn => "internal_warn",
+            Self::Unknown(group) => group,
+        }
+    }
+
+    /// Returns the position of the category in `CATEGORY_PRECEDENCE`, unknown categories come after
+    /// all known ones
+    fn precedence(&self) -> usize {
+        CATEGORY_PRECEDENCE
+            .iter()
+            .position(|c| c == self)
+            .unwrap_or(CATEGORY_PRECEDENCE.len())
+    }
+
+    /// Returns the level lints of this category are emitted at by default, matching
+    /// `declare_clippy_lint!`. Returns `None` for unknown categories.
+    #[must_use]
+    pub fn default_level(&self) -> Option<&'static str> {
+        match self {
+            Self::Correctness => Some("deny"),
+            Self::Suspicious | Self::Style | Self::Complexity | Self::Perf | Self::InternalWarn => Some("warn"),
+            Self::Pedantic | Self::Restriction | Self::Nursery | Self::Cargo | Self::Internal => Some("allow"),
+            Self::Unknown(_) => None,
+        }
+    }
+}
+
+/// The order in which the lint categories are displayed, from the most to the least severe
+pub const CATEGORY_PRECEDENCE: &[LintCategory] = &[
+    LintCategory::Correctness,
+    LintCategory::Suspicious,
+    LintCategory::Style,
+    LintCategory::Complexity,
+    LintCategory::Perf,
+    LintCategory::Pedantic,
+    LintCategory::Restriction,
+    LintCategory::Nursery,
+    LintCategory::Cargo,
+    LintCategory::Internal,
+    LintCategory::InternalWarn,
+];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeprecatedLint {
+    pub name: String,
+    pub reason: String,
+    pub declaration_range: Range<usize>,
+    /// The lowercased name of the lint replacing this one, declared as `pub LINT_NAME => NEW_NAME`
+    pub replacement: Option<String>,
+}
+impl DeprecatedLint {
+    fn new(name: &str, reason: &str, declaration_range: Range<usize>) -> Self {
+        Self {
+            name: name.to_lowercase(),
+            reason: remove_line_splices(reason),
+            declaration_range,
+            replacement: None,
+        }
+    }
+
+    /// Returns the link to the lint's documentation. Deprecated lints are listed on the same page
+    /// as all other lints.
+    #[must_use]
+    pub fn url(&self) -> String {
+        self.url_with_base(DOCS_LINK)
+    }
+
+    /// Returns the link to the lint's documentation on the lint list at `base`, see
+    /// `Lint::url_with_base`
+    #[must_use]
+    pub fn url_with_base(&self, base: &str) -> String {
+        self.url_with(base, UrlScheme::default())
+    }
+
+    /// Returns the link to the lint's documentation on the lint list at `base`, see
+    /// `Lint::url_with`
+    #[must_use]
+    pub fn url_with(&self, base: &str, scheme: UrlScheme) -> String {
+        scheme.url(base, &self.name)
+    }
+
+    /// Returns the names of the deprecated lints along with their deprecation reason, sorted by
+    /// name
+    #[must_use]
+    pub fn reasons(lints: &[Self]) -> Vec<(&str, &str)> {
+        let mut reasons: Vec<_> = lints.iter().map(|l| (&*l.name, &*l.reason)).collect();
+        reasons.sort_unstable();
+        reasons
+    }
+}
+
+/// Formats the lint on a single line as `name [deprecated]: reason`.
+///
+/// ```
+/// use clippy_dev::update_lints::DeprecatedLint;
+///
+/// let lint = DeprecatedLint {
+///     name: "should_assert_eq".into(),
+///     reason: "`assert!()` will be more flexible with RFC 2011".into(),
+///     declaration_range: 0..0,
+///     replacement: None,
+/// };
+/// assert_eq!(
+///     "should_assert_eq [deprecated]: `assert!()` will be more flexible with RFC 2011",
+///     lint.to_string()
+/// );
+/// ```
+impl fmt::Display for DeprecatedLint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [deprecated]: {}", self.name, self.reason)
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RenamedLint {
+    pub old_name: String,
+    pub new_name: String,
+}
+impl RenamedLint {
+    fn new(old_name: &str, new_name: &str) -> Self {
+        Self {
+            old_name: remove_line_splices(old_name),
+            new_name: remove_line_splices(new_name),
+        }
+    }
+}
+
+/// Generates the `register_removed` code
+#[must_use]
+#[cfg(feature = "fs")]
+fn gen_deprecated(lints: &[DeprecatedLint]) -> String {
+    let mut output = GENERATED_FILE_COMMENT.to_string();
+    output.push_str("{\n");
+    for lint in lints {
+        let _: fmt::Result = write!(
+            output,
+            concat!(
+                "    store.register_removed(\n",
+                "        \"clippy::{}\",\n",
+                "        \"{}\",\n",
+                "    );\n"
+            ),
+            lint.name, lint.reason,
+        );
+    }
+    output.push_str("}\n");
+
+    output
+}
+
+/// Generates the code for registering lints
+#[must_use]
+#[cfg(feature = "fs")]
+fn gen_declared_lints<'a>(
+    internal_lints: impl Iterator<Item = &'a Lint>,
+    usable_lints: impl Iterator<Item = &'a Lint>,
+) -> String {
+    let mut details: Vec<_> = internal_lints
+        .map(|l| (false, &l.module, l.name.to_uppercase()))
+        .chain(usable_lints.map(|l| (true, &l.module, l.name.to_uppercase())))
+        .collect();
+    details.sort_unstable();
+
+    let mut output = GENERATED_FILE_COMMENT.to_string();
+    output.push_str("pub(crate) static LINTS: &[&crate::LintInfo] = &[\n");
+
+    for (is_public, module_name, lint_name) in details {
+        if !is_public {
+            output.push_str("    #[cfg(feature = \"internal\")]\n");
+        }
+        let _: fmt::Result = writeln!(output, "    crate::{module_name}::{lint_name}_INFO,");
+    }
+    output.push_str("];\n");
+
+    output
+}
+
+/// Returns the link to the lint list filtered to a single lint group. The lint list takes the
+/// groups to show in the `groups` query parameter, e.g. `index.html?groups=pedantic`.
+#[must_use]
+pub fn group_url(group: &str) -> String {
+    format!("{DOCS_LINK}?groups={group}")
+}
+
+/// Returns the filtered lint list link, see `group_url`, of every lint group with at least one lint
+#[must_use]
+pub fn group_urls(lints: &[Lint]) -> BTreeMap<String, String> {
+    lints
+        .iter()
+        .map(|lint| (lint.group.clone(), group_url(&lint.group)))
+        .collect()
+}
+
+/// Generates a markdown page for a single lint with its name, group, description and a link to the
+/// other lints of its group
+#[must_use]
+pub fn render_lint_page(lint: &Lint) -> String {
+    let mut output = String::new();
+    let _: fmt::Result = writeln!(output, "# {}\n", lint.name);
+    let _: fmt::Result = writeln!(output, "`{}`\n", lint.group);
+    let _: fmt::Result = writeln!(output, "{}\n", lint.desc);
+    let _: fmt::Result = writeln!(output, "[All {} lints]({})", lint.group, group_url(&lint.group));
+    output
+}
+
+/// Generates a markdown page for a single deprecated lint with a notice containing the deprecation
+/// reason, see `render_lint_page`
+#[must_use]
+pub fn render_deprecated_lint_page(lint: &DeprecatedLint) -> String {
+    let mut output = String::new();
+    let _: fmt::Result = writeln!(output, "# {}\n", lint.name);
+    let _: fmt::Result = writeln!(output, "`deprecated`\n");
+    let _: fmt::Result = writeln!(output, "> **Deprecated:** {}\n", lint.reason);
+    let _: fmt::Result = writeln!(output, "[All lints]({DOCS_LINK})");
+    output
+}
+
+/// Generates a markdown table of all usable lints, sorted by name
+#[must_use]
+pub fn render_lint_table(lints: &[Lint]) -> String {
+    let mut output = String::from("| Lint | Group | Description |\n| --- | --- | --- |\n");
+    for lint in Lint::usable_lints(lints).iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        let _: fmt::Result = writeln!(
+            output,
+            "| [`{}`]({}) | {} | {} |",
+            lint.name,
+            lint.url(),
+            lint.group,
+            lint.desc.replace('|', "\\|"),
+        );
+    }
+    output
+}
+
+/// Generates a markdown table of the deprecated lints with their deprecation reason and the lint
+/// replacing them, if any, sorted by name
+#[must_use]
+pub fn render_deprecated_table(lints: &[DeprecatedLint]) -> String {
+    let mut output = String::from("| Lint | Reason | Replacement |\n| --- | --- | --- |\n");
+    for lint in lints.iter().sorted_by(|a, b| a.name.cmp(&b.name)) {
+        let _: fmt::Result = writeln!(
+            output,
+            "| [`{}`]({}) | {} | {} |",
+            lint.name,
+            lint.url(),
+            lint.reason.replace('|', "\\|"),
+            lint.replacement
+                .as_ref()
+                .map_or_else(String::new, |replacement| format!("`{replacement}`")),
+        );
+    }
+    output
+}
+
+/// Generates a CSV file with a row for every lint, including deprecated lints
+///
+/// The columns are `name,group,desc,deprecation,module`. Deprecated lints are put in the
+/// `deprecated` group and `deprecated_lints` module, with the deprecation reason in the
+/// `deprecation` column.
+#[must_use]
+pub fn lints_to_csv(lints: &[Lint], deprecated_lints: &[DeprecatedLint]) -> String {
+    fn escape(field: &str) -> Cow<'_, str> {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\"")).into()
+        } else {
+            field.into()
+        }
+    }
+
+    let mut output = String::from("name,group,desc,deprecation,module\n");
+    for lint in lints {
+        let _: fmt::Result = writeln!(
+            output,
+            "{},{},{},,{}",
+            escape(&lint.name),
+            escape(&lint.group),
+            escape(&lint.desc),
+            escape(&lint.module),
+        );
+    }
+    for lint in deprecated_lints {
+        let _: fmt::Result = writeln!(
+            output,
+            "{},deprecated,,{},deprecated_lints",
+            escape(&lint.name),
+            escape(&lint.reason),
+        );
+    }
+    output
+}
+
+/// Renders every field of the lints in a canonical format, one line per lint sorted by module and
+/// then by name, to compare the parser's output against a committed snapshot. Strings are quoted
+/// and escaped like Rust string literals, so each lint stays on one line.
+#[must_use]
+pub fn render_snapshot(lints: &[Lint]) -> String {
+    let mut sorted: Vec<&Lint> = lints.iter().collect();
+    sorted.sort();
+
+    let mut output = String::new();
+    for lint in sorted {
+        let _: fmt::Result = writeln!(
+            output,
+            "{} {:?} group={:?} desc={:?} raw_desc={:?} range={:?} line={} applicability={:?} default_level={:?}",
+            lint.module,
+            lint.name,
+            lint.group,
+            lint.desc,
+            lint.raw_desc,
+            lint.declaration_range,
+            lint.line,
+            lint.applicability,
+            lint.default_level,
+        );
+    }
+    output
+}
+
+/// Returns a hash of all fields of the lints which doesn't depend on their order. It's computed
+/// with 64-bit FNV-1a over `render_snapshot`, so it's the same across runs, platforms and Rust
+/// versions.
+#[must_use]
+pub fn lints_hash(lints: &[Lint]) -> u64 {
+    render_snapshot(lints)
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+#[cfg(feature = "fs")]
+fn gen_deprecated_lints_test(lints: &[DeprecatedLint]) -> String {
+    let mut res: String = GENERATED_FILE_COMMENT.into();
+    for lint in lints {
+        writeln!(res, "#![warn(clippy::{})]", lint.name).unwrap();
+    }
+    res.push_str("\nfn main() {}\n");
+    res
+}
+
+#[cfg(feature = "fs")]
+fn gen_renamed_lints_test(lints: &[RenamedLint]) -> String {
+    let mut seen_lints = HashSet::new();
+    let mut res: String = GENERATED_FILE_COMMENT.into();
+    for lint in lints {
+        if seen_lints.insert(&lint.new_name) {
+            writeln!(res, "#![allow({})]", lint.new_name).unwrap();
+        }
+    }
+    seen_lints.clear();
+    for lint in lints {
+        if seen_lints.insert(&lint.old_name) {
+            writeln!(res, "#![warn({})]", lint.old_name).unwrap();
+        }
+    }
+    res.push_str("\nfn main() {}\n");
+    res
+}
+
+#[cfg(feature = "fs")]
+fn gen_renamed_lints_list(lints: &[RenamedLint]) -> String {
+    const HEADER: &str = "\
+        // This file is managed by `cargo dev rename_lint`. Prefer using that when possible.\n\n\
+        #[rustfmt::skip]\n\
+        pub static RENAMED_LINTS: &[(&str, &str)] = &[\n";
+
+    let mut res = String::from(HEADER);
+    for lint in lints {
+        writeln!(res, "    (\"{}\", \"{}\"),", lint.old_name, lint.new_name).unwrap();
+    }
+    res.push_str("];\n");
+    res
+}
+
+/// Serializes the lints into a JSON array
+///
+/// # Panics
+///
+/// Panics if the lints could not be serialized
+#[cfg(feature = "serde")]
+#[must_use]
+pub fn lints_to_json<T: serde::Serialize>(lints: &[T]) -> String {
+    serde_json::to_string(lints).expect("failed to serialize lints")
+}
+
+/// Parses lints from a JSON array as produced by `lints_to_json`
+///
+/// # Errors
+///
+/// Returns an error if `s` is not a valid JSON array of lints
+#[cfg(feature = "serde")]
+pub fn lints_from_json<T: serde::de::DeserializeOwned>(s: &str) -> Result<Vec<T>, serde_json::Error> {
+    serde_json::from_str(s)
+}
+
+/// Generates a JSON object mapping each lint's name to its `group`, `desc` and docs `url`.
+/// Deprecated lints are put in the `deprecated` group, with the deprecation reason in an additional
+/// `deprecation` field.
+///
+/// # Panics
+///
+/// Panics if the index could not be serialized
+#[cfg(feature = "serde")]
+#[must_use]
+pub fn render_lint_index_json(lints: &[Lint], deprecated_lints: &[DeprecatedLint]) -> String {
+    let mut index = BTreeMap::new();
+    for lint in lints {
+        index.insert(
+            &*lint.name,
+            serde_json::json!({ "group": lint.group, "desc": lint.desc, "url": lint.url() }),
+        );
+    }
+    for lint in deprecated_lints {
+        index.insert(
+            &*lint.name,
+            serde_json::json!({
+                "group": "deprecated",
+                "desc": "",
+                "url": lint.url(),
+                "deprecation": lint.reason,
+            }),
+        );
+    }
+    serde_json::to_string_pretty(&index).expect("failed to serialize lint index")
+}
+
+/// A generated file which differs from the freshly generated contents.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mismatch {
+    pub path: PathBuf,
+    /// The 1-based number of the first line which differs
+    pub line: usize,
+    /// The line in the file, or `None` if the file has fewer lines
+    pub expected: Option<String>,
+    /// The generated line, or `None` if fewer lines were generated
+    pub generated: Option<String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is out of date on line {}: expected `{}`, generated `{}`",
+            self.path.display(),
+            self.line,
+            self.expected.as_deref().unwrap_or("<end of file>"),
+            self.generated.as_deref().unwrap_or("<end of file>"),
+        )
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Checks that the file at `expected_path` matches the output of `generate`. A missing file is
+/// treated as empty.
+///
+/// # Errors
+///
+/// Returns the first differing line if the file doesn't match the generated contents
+///
+/// # Panics
+///
+/// Panics if the file exists but could not be read from
+#[cfg(feature = "fs")]
+pub fn check_generated(expected_path: impl AsRef<Path>, generate: impl Fn() -> String) -> Result<(), Mismatch> {
+    let path = expected_path.as_ref();
+    let expected = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => panic!("Cannot read from `{}`: {e}", path.display()),
+    };
+    let generated = generate();
+    if expected == generated {
+        return Ok(());
+    }
+
+    let mut expected_lines = expected.split('\n');
+    let mut generated_lines = generated.split('\n');
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), generated_lines.next()) {
+            (e, g) if e != g => {
+                return Err(Mismatch {
+                    path: path.to_path_buf(),
+                    line,
+                    expected: e.map(String::from),
+                    generated: g.map(String::from),
+                });
+            },
+            _ => line += 1,
+        }
+    }
+}
+
+/// The regular, deprecated and renamed lints found in the lint source files.
+pub type GatheredLints = (Vec<Lint>, Vec<DeprecatedLint>, Vec<RenamedLint>);
+
+/// Gathers all lints defined in `clippy_lints/src`
+#[cfg(feature = "fs")]
+fn gather_all() -> GatheredLints {
+    gather_all_from(clippy_project_root().join("clippy_lints/src"))
+}
+
+/// Gathers all lints defined in the source files under `root`
+///
+/// Module names are derived from the file paths relative to `root`.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_all_from(root: impl AsRef<Path>) -> GatheredLints {
+    try_gather_all_from(root).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Gathers all lints defined in `clippy_lints/src`, sorted by module and name so the order doesn't
+/// depend on the file system.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Panics
+///
+/// Panics if a file could not be read from
+#[must_use]
+#[cfg(feature = "fs")]
+pub fn gather_all_sorted() -> Vec<Lint> {
+    gather_all_sorted_from(clippy_project_root().join("clippy_lints/src"))
+}
+
+/// Gathers all lints defined in the source files under `root`, sorted by module and name.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_all_sorted_from(root: impl AsRef<Path>) -> Vec<Lint> {
+    let (mut lints, _, _) = gather_all_from(root);
+    lints.sort();
+    lints
+}
+
+/// Somewhere lints can be gathered from, e.g. a source tree.
+pub trait LintSource {
+    /// Returns the lints of this source, not including deprecated and renamed lints
+    fn lints(&self) -> Vec<Lint>;
+}
+
+/// The lints declared in the source files under `root`, see `gather_all_from`.
+#[derive(Clone, Debug)]
+#[cfg(feature = "fs")]
+pub struct FsLintSource {
+    pub root: PathBuf,
+}
+
+#[cfg(feature = "fs")]
+impl LintSource for FsLintSource {
+    /// # Panics
+    ///
+    /// Panics if a file under `root` could not be read from
+    fn lints(&self) -> Vec<Lint> {
+        gather_all_from(&self.root).0
+    }
+}
+
+/// What `gather_all_from_roots` does when a lint name is declared under more than one root
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OnCollision {
+    /// Keep the lint from the root listed first
+    PreferFirst,
+    /// Fail with a `LintCollision`
+    Error,
+}
+
+/// A lint name declared under more than one root.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LintCollision {
+    pub name: String,
+    /// The root the lint was first found under
+    pub first: PathBuf,
+    /// The later root also declaring the lint
+    pub second: PathBuf,
+}
+
+impl fmt::Display for LintCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Lint `{}` is declared in both `{}` and `{}`",
+            self.name,
+            self.first.display(),
+            self.second.display()
+        )
+    }
+}
+
+impl std::error::Error for LintCollision {}
+
+/// Gathers the lints defined under each of `roots`, in order. Lints declared more than once under
+/// the same root are all kept, names declared under multiple roots are handled according to
+/// `on_collision`.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Errors
+///
+/// Returns the first collision if `on_collision` is `OnCollision::Error`
+///
+/// # Panics
+///
+/// Panics if a file under one of the roots could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_all_from_roots(roots: &[PathBuf], on_collision: OnCollision) -> Result<Vec<Lint>, LintCollision> {
+    let mut lints = Vec::new();
+    let mut seen: HashMap<String, &PathBuf> = HashMap::new();
+    for root in roots {
+        let (root_lints, _, _) = gather_all_from(root);
+        let mut root_names = HashSet::new();
+        for lint in root_lints {
+            if let Some(&first) = seen.get(&lint.name) {
+                match on_collision {
+                    OnCollision::PreferFirst => continue,
+                    OnCollision::Error => {
+                        return Err(LintCollision {
+                            name: lint.name,
+                            first: first.clone(),
+                            second: root.clone(),
+                        });
+                    },
+                }
+            }
+            root_names.insert(lint.name.clone());
+            lints.push(lint);
+        }
+        seen.extend(root_names.into_iter().map(|name| (name, root)));
+    }
+    Ok(lints)
+}
+
+/// Gathers all lints defined in `clippy_lints/src`, returning an error instead of panicking if a
+/// file could not be read.
+///
+/// # Errors
+///
+/// Returns the path of the offending file if it could not be read from
+#[cfg(feature = "fs")]
+pub fn try_gather_all() -> Result<GatheredLints, GatherError> {
+    try_gather_all_from(clippy_project_root().join("clippy_lints/src"))
+}
+
+/// Gathers all lints defined in the source files under `root`, returning an error instead of
+/// panicking if a file could not be read.
+///
+/// # Errors
+///
+/// Returns the path of the offending file if it could not be read from
+///
+/// # Panics
+///
+/// Panics if a file path under `root` is not valid UTF-8
+#[cfg(feature = "fs")]
+pub fn try_gather_all_from(root: impl AsRef<Path>) -> Result<GatheredLints, GatherError> {
+    #[cfg(feature = "rayon")]
+    return try_gather_all_par(root.as_ref());
+    #[cfg(not(feature = "rayon"))]
+    return try_gather_all_seq(root.as_ref());
+}
+
+#[cfg_attr(feature = "rayon", allow(dead_code))]
+#[cfg(feature = "fs")]
+fn try_gather_all_seq(root: &Path) -> Result<GatheredLints, GatherError> {
+    #[cfg(feature = "timings")]
+    let (start, mut files) = (std::time::Instant::now(), 0);
+    let mut lints = (Vec::with_capacity(1000), Vec::with_capacity(50), Vec::with_capacity(50));
+    for file in try_lint_files(root) {
+        let (rel_path, file) = file?;
+        gather_from_file(&rel_path, file.path(), &mut lints)?;
+        #[cfg(feature = "timings")]
+        {
+            files += 1;
+        }
+    }
+    #[cfg(feature = "timings")]
+    log_timings(files, &lints, start);
+    Ok(lints)
+}
+
+/// Parses the files in parallel. Each file is parsed independently, the results are merged
+/// afterwards.
+#[cfg(feature = "rayon")]
+#[cfg(feature = "fs")]
+fn try_gather_all_par(root: &Path) -> Result<GatheredLints, GatherError> {
+    use rayon::prelude::*;
+
+    #[cfg(feature = "timings")]
+    let start = std::time::Instant::now();
+    let files = try_lint_files(root).collect::<Result<Vec<_>, _>>()?;
+    let lints = files
+        .par_iter()
+        .try_fold(GatheredLints::default, |mut lints, (rel_path, file)| {
+            gather_from_file(rel_path, file.path(), &mut lints).map(|()| lints)
+        })
+        .try_reduce(GatheredLints::default, |mut lints, (other, deprecated, renamed)| {
+            lints.0.extend(other);
+            lints.1.extend(deprecated);
+            lints.2.extend(renamed);
+            Ok(lints)
+        })?;
+    #[cfg(feature = "timings")]
+    log_timings(files.len(), &lints, start);
+    Ok(lints)
+}
+
+#[cfg(feature = "timings")]
+#[cfg(feature = "fs")]
+fn log_timings(files: usize, lints: &GatheredLints, start: std::time::Instant) {
+    log::info!(
+        "gathered {} lints, {} deprecated and {} renamed lints from {files} files in {:?}",
+        lints.0.len(),
+        lints.1.len(),
+        lints.2.len(),
+        start.elapsed(),
+    );
+}
+
+/// Parses a single lint source file, adding the lints found to `lints`
+///
+/// `rel_path` is the path of the file relative to the lint source root, it's used to determine
+/// the lint's module.
+#[cfg(feature = "fs")]
+fn gather_from_file(rel_path: &Path, path: &Path, lints: &mut GatheredLints) -> Result<(), GatherError> {
+    let contents = fs::read(path)
+        .and_then(|bytes| String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+        .map_err(|error| GatherError {
+            path: path.to_path_buf(),
+            error,
+        })?;
+    let contents = strip_bom(&contents);
+    #[cfg(feature = "timings")]
+    let before = lints.0.len() + lints.1.len() + lints.2.len();
+    parse_module(&module_name(rel_path), contents, lints);
+    #[cfg(feature = "timings")]
+    log::debug!(
+        "{}: {} lints",
+        path.display(),
+        lints.0.len() + lints.1.len() + lints.2.len() - before
+    );
+    Ok(())
+}
+
+/// Parses the contents of a lint source file, dispatching on the module name
+fn parse_module(module: &str, contents: &str, lints: &mut GatheredLints) {
+    match module {
+        "deprecated_lints" => parse_deprecated_contents(contents, &mut lints.1),
+        "renamed_lints" => parse_renamed_contents(contents, &mut lints.2),
+        _ => parse_contents(contents, module, &mut lints.0),
+    }
+}
+
+/// Gathers the lints from in-memory `(module_name, contents)` pairs, the same way they would be
+/// gathered from the corresponding source files.
+///
+/// Deprecated and renamed lints are skipped.
+pub fn gather_from_sources<'a>(sources: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<Lint> {
+    let mut lints = GatheredLints::default();
+    for (module, contents) in sources {
+        parse_module(module, contents, &mut lints);
+    }
+    lints.0
+}
+
+/// Gathers the lints from the source code read from `reader`, e.g. stdin, as if it was the contents
+/// of `module`. See `gather_from_sources`.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or the source code isn't valid UTF-8
+pub fn gather_from_reader<R: Read>(mut reader: R, module: &str) -> io::Result<Vec<Lint>> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+    Ok(gather_from_sources([(module, strip_bom(&contents))]))
+}
+
+/// A lint declared by either `declare_clippy_lint!` or `declare_deprecated_lint!`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LintDeclaration {
+    Lint(Lint),
+    Deprecated(DeprecatedLint),
+}
+
+impl LintDeclaration {
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Lint(lint) => &lint.name,
+            Self::Deprecated(lint) => &lint.name,
+        }
+    }
+
+    #[must_use]
+    pub fn declaration_range(&self) -> &Range<usize> {
+        match self {
+            Self::Lint(lint) => &lint.declaration_range,
+            Self::Deprecated(lint) => &lint.declaration_range,
+        }
+    }
+}
+
+/// Parses both the `declare_clippy_lint!` and the `declare_deprecated_lint!` invocations in
+/// `contents`, returning them in the order they are declared in.
+///
+/// Unlike gathering from files, both kinds are parsed regardless of `module`.
+#[must_use]
+pub fn parse_in_source_order(contents: &str, module: &str) -> Vec<LintDeclaration> {
+    let mut lints = Vec::new();
+    let mut deprecated = Vec::new();
+    parse_contents(contents, module, &mut lints);
+    parse_deprecated_contents(contents, &mut deprecated);
+
+    let mut declarations: Vec<_> = lints
+        .into_iter()
+        .map(LintDeclaration::Lint)
+        .chain(deprecated.into_iter().map(LintDeclaration::Deprecated))
+        .collect();
+    declarations.sort_by_key(|d| d.declaration_range().start);
+    declarations
+}
+
+/// Removes the UTF-8 byte order mark at the start of a file, if there is one
+fn strip_bom(contents: &str) -> &str {
+    contents.strip_prefix('\u{feff}').unwrap_or(contents)
+}
+
+/// Returns the module name of a lint source file from its path relative to the lint source root
+#[cfg(feature = "fs")]
+fn module_name(rel_path: &Path) -> String {
+    let module = rel_path
+        .components()
+        .map(|c| c.as_os_str().to_str().unwrap())
+        .collect::<Vec<_>>()
+        .join("::");
+
+    // If the lints are stored in mod.rs, we get the module name from
+    // the containing directory:
+    if let Some(module) = module.strip_suffix("::mod.rs") {
+        module.into()
+    } else {
+        module.strip_suffix(".rs").unwrap_or(&module).into()
+    }
+}
+
+/// Lazily gathers the lints defined in the source files under `root`, reading one file at a time.
+/// Only the contents of the file currently being parsed are kept in memory.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_lints_lazy(root: impl AsRef<Path>) -> impl Iterator<Item = Lint> {
+    lint_files(root.as_ref()).flat_map(|(rel_path, file)| lints_in_file(&rel_path, file.path()))
+}
+
+#[cfg(feature = "fs")]
+fn lints_in_file(rel_path: &Path, path: &Path) -> Vec<Lint> {
+    let mut lints = GatheredLints::default();
+    gather_from_file(rel_path, path, &mut lints).unwrap_or_else(|e| panic!("{e}"));
+    lints.0
+}
+
+/// Gathers the lints of the files under `root` which were modified after `since`, returning the
+/// lints of each file separately so they can be merged with previous results. Files whose
+/// modification time can't be read are always included.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+#[must_use]
+pub fn gather_all_since(root: impl AsRef<Path>, since: SystemTime) -> Vec<(PathBuf, Vec<Lint>)> {
+    let mut files: Vec<_> = lint_files(root.as_ref())
+        .filter(|(_, file)| {
+            file.metadata()
+                .ok()
+                .and_then(|metadata| metadata.modified().ok())
+                .map_or(true, |modified| modified > since)
+        })
+        .map(|(rel_path, file)| {
+            let lints = lints_in_file(&rel_path, file.path());
+            (file.into_path(), lints)
+        })
+        .collect();
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    files
+}
+
+/// Returns the files found by `lint_files` under `root` which don't declare any lint, sorted. This
+/// includes deprecated and renamed lints. Files whose path relative to `root`, with `/` as the
+/// separator, is in `allow` are skipped, e.g. `lib.rs` or `utils/mod.rs`.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+#[must_use]
+#[allow(clippy::implicit_hasher)]
+pub fn files_without_lints(root: impl AsRef<Path>, allow: &HashSet<String>) -> Vec<PathBuf> {
+    let mut files: Vec<_> = lint_files(root.as_ref())
+        .filter(|(rel_path, _)| {
+            let rel_path = rel_path.iter().map(|c| c.to_string_lossy()).join("/");
+            !allow.contains(&rel_path)
+        })
+        .filter_map(|(rel_path, file)| {
+            let mut lints = GatheredLints::default();
+            gather_from_file(&rel_path, file.path(), &mut lints).unwrap_or_else(|e| panic!("{e}"));
+            (lints.0.is_empty() && lints.1.is_empty() && lints.2.is_empty()).then(|| file.into_path())
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Gathers the lints from all modules under `root` whose name matches the shell-style glob
+/// `pattern`. Only the matching files are read.
+///
+/// `*` matches any sequence of characters and `?` matches a single character. The pattern is
+/// matched against the full module name, e.g. `methods::bind_instead_of_map`.
+///
+/// # Panics
+///
+/// Panics if a matching file could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_from_modules(root: impl AsRef<Path>, pattern: &str) -> impl Iterator<Item = Lint> + '_ {
+    lint_files(root.as_ref())
+        .filter(move |(rel_path, _)| glob_match(pattern, &module_name(rel_path)))
+        .flat_map(|(rel_path, file)| lints_in_file(&rel_path, file.path()))
+}
+
+/// Lazily gathers the lints under `root` for which `pred` returns `true`. Lints are filtered as
+/// each file is parsed, so the ones which don't match are never collected.
+///
+/// Deprecated and renamed lints are skipped.
+///
+/// # Panics
+///
+/// Panics if a file under `root` could not be read from
+#[cfg(feature = "fs")]
+pub fn gather_filtered<F: Fn(&Lint) -> bool>(root: impl AsRef<Path>, pred: F) -> impl Iterator<Item = Lint> {
+    gather_lints_lazy(root).filter(move |lint| pred(lint))
+}
+
+/// Matches `text` against a shell-style glob supporting `*` and `?`
+#[cfg(feature = "fs")]
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<_> = pattern.chars().collect();
+    let text: Vec<_> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // The position of the last `*` in the pattern and the text position it was tried at
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            },
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            },
+            _ => match backtrack {
+                // Let the last `*` match one more character
+                Some((star_p, star_t)) => {
+                    backtrack = Some((star_p, star_t + 1));
+                    p = star_p + 1;
+                    t = star_t + 1;
+                },
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// An error encountered while reading the lint source files.
+#[derive(Debug)]
+pub struct GatherError {
+    /// The file or directory which could not be read
+    pub path: PathBuf,
+    pub error: io::Error,
+}
+
+impl GatherError {
+    /// Checks if the file could be read, but isn't valid UTF-8
+    #[must_use]
+    pub fn is_invalid_utf8(&self) -> bool {
+        matches!(self.error.get_ref(), Some(e) if e.is::<std::string::FromUtf8Error>())
+    }
+}
+
+impl fmt::Display for GatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cannot read from `{}`: {}", self.path.display(), self.error)
+    }
+}
+
+impl std::error::Error for GatherError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// A malformed `declare_clippy_lint!` invocation found by [`parse_contents_strict`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub module: String,
+    /// The 1-based line of the start of the invocation
+    pub line: usize,
+    /// The byte range of the invocation in the parsed contents, from the macro name up to the
+    /// matching closing delimiter or the end of the contents if there is none
+    pub span: Range<usize>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Malformed `declare_clippy_lint!` in module `{}` on line {}",
+            self.module, self.line
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The directories skipped by `lint_files`, as they may contain fixtures which look like lint
+/// declarations
+#[cfg(feature = "fs")]
+pub const IGNORED_DIRS: &[&str] = &["tests", "target"];
+
+/// Returns all `.rs` files under `root` along with their path relative to `root`, skipping the
+/// directories in `IGNORED_DIRS`
+///
+/// # Panics
+///
+/// Panics if `root` could not be walked
+#[cfg(feature = "fs")]
+pub fn lint_files(root: &Path) -> impl Iterator<Item = (PathBuf, DirEntry)> {
+    lint_files_ignoring(root, IGNORED_DIRS)
+}
+
+/// Returns all `.rs` files under `root` along with their path relative to `root`, skipping the
+/// directories named in `ignored_dirs`
+///
+/// # Panics
+///
+/// Panics if `root` could not be walked
+#[cfg(feature = "fs")]
+pub fn lint_files_ignoring(root: &Path, ignored_dirs: &[&str]) -> impl Iterator<Item = (PathBuf, DirEntry)> {
+    try_lint_files_ignoring(root, ignored_dirs).map(|f| f.unwrap_or_else(|e| panic!("{e}")))
+}
+
+/// Returns the path of the source file under `root` which declares the lints of `module`, e.g.
+/// `root/methods/mod.rs` for `methods`. The files are found like `lint_files`.
+///
+/// If no module has exactly that name, `module` is also matched against the last component of the
+/// module names, so `utils` can find `root/matches/utils.rs`. When several files match, the first
+/// one ordered by path is returned.
+///
+/// # Panics
+///
+/// Panics if `root` could not be walked
+#[cfg(feature = "fs")]
+pub fn module_path(root: impl AsRef<Path>, module: &str) -> Option<PathBuf> {
+    let mut exact: Option<PathBuf> = None;
+    let mut by_stem: Option<PathBuf> = None;
+    for (rel_path, file) in lint_files(root.as_ref()) {
+        let name = module_name(&rel_path);
+        let found = if name == module {
+            &mut exact
+        } else if name.rsplit("::").next() == Some(module) {
+            &mut by_stem
+        } else {
+            continue;
+        };
+        if found.as_ref().map_or(true, |p| file.path() < p.as_path()) {
+            *found = Some(file.into_path());
+        }
+    }
+    exact.or(by_stem)
+}
+
+#[cfg(feature = "fs")]
+fn try_lint_files(root: &Path) -> impl Iterator<Item = Result<(PathBuf, DirEntry), GatherError>> {
+    try_lint_files_ignoring(root, IGNORED_DIRS)
+}
+
+#[cfg(feature = "fs")]
+fn try_lint_files_ignoring(
+    root: &Path,
+    ignored_dirs: &[&str],
+) -> impl Iterator<Item = Result<(PathBuf, DirEntry), GatherError>> {
+    let root_path = root.to_path_buf();
+    let ignored_dirs: Vec<String> = ignored_dirs.iter().map(|&dir| dir.into()).collect();
+    let iter = WalkDir::new(&root_path).into_iter().filter_entry(move |e| {
+        e.depth() == 0 || !e.file_type().is_dir() || !ignored_dirs.iter().any(|dir| e.file_name() == &**dir)
+    });
+    iter.filter_map(move |f| match f {
+        Ok(f) if f.path().extension() == Some(OsStr::new("rs")) => {
+            Some(Ok((f.path().strip_prefix(&root_path).unwrap().to_path_buf(), f)))
+        },
+        Ok(_) => None,
+        Err(e) => Some(Err(GatherError {
+            path: e.path().unwrap_or(&root_path).to_path_buf(),
+            error: e.into(),
+        })),
+    })
+}
+
+macro_rules! match_tokens {
+    ($iter:ident, $($token:ident $({$($fields:tt)*})? $(($capture:ident))?)*) => {
+         {
+            $(#[allow(clippy::redundant_pattern)] let Some(LintDeclSearchResult {
+                    token_kind: TokenKind::$token $({$($fields)*})?,
+                    content: $($capture @)? _,
+                    ..
+            }) = $iter.next() else {
+                continue;
+            };)*
+            #[allow(clippy::unused_unit)]
+            { ($($($capture,)?)*) }
+        }
+    }
+}
+
+pub(crate) use match_tokens;
+
+pub(crate) struct LintDeclSearchResult<'a> {
+    pub token_kind: TokenKind,
+    pub content: &'a str,
+    pub range: Range<usize>,
+}
+
+/// Parse a source file looking for `declare_clippy_lint` macro invocations.
+fn parse_contents(contents: &str, module: &str, lints: &mut Vec<Lint>) {
+    parse_macro_invocations(contents, module, "declare_clippy_lint", lints);
+}
+
+/// Parses a source file looking for invocations of the macro called `macro_name`, e.g. a wrapper
+/// around `declare_clippy_lint!` in another crate. The name is given without the `!`. The
+/// invocations must use the same syntax as `declare_clippy_lint!`, i.e. `pub NAME, group,
+/// "description"` preceded by doc comments and attributes. The lints are normalized the same way.
+///
+/// # Errors
+///
+/// Returns an error if `macro_name` isn't an identifier, e.g. `my_macro!` or an empty string
+pub fn parse_with_macro(contents: &str, module: &str, macro_name: &str) -> Result<Vec<Lint>, InvalidMacroName> {
+    if !rustc_lexer::is_ident(macro_name) {
+        return Err(InvalidMacroName(macro_name.into()));
+    }
+    let mut lints = Vec::new();
+    parse_macro_invocations(contents, module, macro_name, &mut lints);
+    Ok(lints)
+}
+
+/// The macro name passed to `parse_with_macro` isn't an identifier.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InvalidMacroName(pub String);
+
+impl fmt::Display for InvalidMacroName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` isn't a valid macro name, expected an identifier", self.0)
+    }
+}
+
+impl std::error::Error for InvalidMacroName {}
+
+fn parse_macro_invocations(contents: &str, module: &str, macro_name: &str, lints: &mut Vec<Lint>) {
+    let mut offset = 0usize;
+    let mut iter = tokenize(contents).map(|t| {
+        let range = offset..offset + t.len as usize;
+        offset = range.end;
+
+        LintDeclSearchResult {
+            token_kind: t.kind,
+            content: &contents[range.clone()],
+            range,
+        }
+    });
+
+    while let Some(LintDeclSearchResult { range, .. }) = iter.find(
+        |LintDeclSearchResult {
+             token_kind, content, ..
+         }| token_kind == &TokenKind::Ident && *content == macro_name,
+    ) {
+        let start = range.start;
+        let mut iter = iter.by_ref().filter(|t| {
+            !matches!(
+                t.token_kind,
+                TokenKind::Whitespace
+                    | TokenKind::LineComment { doc_style: None }
+                    | TokenKind::BlockComment { doc_style: None, .. }
+            )
+        });
+        // matches `!{` or `!(`
+        match_tokens!(iter, Bang);
+        let close = match iter.next() {
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::OpenBrace,
+                ..
+            }) => TokenKind::CloseBrace,
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::OpenParen,
+                ..
+            }) => TokenKind::CloseParen,
+            _ => continue,
+        };
+
+        // /// doc comments and attributes, e.g. #[clippy::version = "version"]
+        let (docs, attrs, next) = parse_docs_and_attributes(&mut iter);
+
+        // pub
+        let Some(LintDeclSearchResult {
+            token_kind: TokenKind::Ident,
+            ..
+        }) = next
+        else {
+            continue;
+        };
+
+        let (name, group) = match_tokens!(
+            iter,
+            // LINT_NAME
+            Ident(name) Comma
+            // group
+            Ident(group)
+        );
+
+        // , "description"
+        let (desc, next) = parse_desc_literals(&mut iter);
+
+        if let Some(LintDeclSearchResult { token_kind, range, .. }) = next
+            && token_kind == close
+        {
+            let declaration_range = start..range.end;
+            let mut lint = if let Some((first, rest)) = desc.split_first() {
+                let mut lint = Lint::new(name, group, first.content, module, declaration_range);
+                if let Some(last) = rest.last() {
+                    for literal in rest {
+                        lint.desc.push_str(&remove_line_splices(literal.content));
+                    }
+                    lint.raw_desc = contents[first.range.start..last.range.end].into();
+                }
+                lint
+            } else if !docs.is_empty() {
+                // Without a string literal the doc comment is used as the description
+                Lint::from_doc_comments(name, group, &docs, module, declaration_range)
+            } else {
+                continue;
+            };
+            lint.line = contents[..start].matches('\n').count() + 1;
+            attrs.apply_to(&mut lint);
+            lints.push(lint);
+        }
+    }
+}
+
+/// Parses a source file looking for `declare_clippy_lint` macro invocations, like
+/// `parse_contents`, but fails if any invocation couldn't be parsed instead of skipping it.
+///
+/// # Errors
+///
+/// Returns an error for every `declare_clippy_lint!` invocation which doesn't declare a lint.
+pub fn parse_contents_strict(contents: &str, module: &str) -> Result<Vec<Lint>, Vec<ParseError>> {
+    let mut lints = Vec::new();
+    parse_contents(contents, module, &mut lints);
+
+    let mut offset = 0usize;
+    let tokens: Vec<_> = tokenize(contents)
+        .map(|t| {
+            let range = offset..offset + t.len as usize;
+            offset = range.end;
+            (t.kind, range)
+        })
+        .filter(|(kind, _)| {
+            !matches!(
+                kind,
+                TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+            )
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    for (i, (kind, range)) in tokens.iter().enumerate() {
+        if *kind == TokenKind::Ident
+            && &contents[range.clone()] == "declare_clippy_lint"
+            && matches!(tokens.get(i + 1), Some((TokenKind::Bang, _)))
+            && !lints.iter().any(|l| l.declaration_range.start == range.start)
+        {
+            // The invocation ends with the delimiter closing the one after `!`
+            let mut depth = 0usize;
+            let mut end = contents.len();
+            for (kind, range) in &tokens[i + 2..] {
+                match kind {
+                    TokenKind::OpenBrace | TokenKind::OpenParen | TokenKind::OpenBracket => depth += 1,
+                    TokenKind::CloseBrace | TokenKind::CloseParen | TokenKind::CloseBracket => {
+                        depth = depth.saturating_sub(1);
+                    },
+                    _ => (),
+                }
+                if depth == 0 {
+                    end = range.end;
+                    break;
+                }
+            }
+            errors.push(ParseError {
+                module: module.into(),
+                line: contents[..range.start].matches('\n').count() + 1,
+                span: range.start..end,
+            });
+        }
+    }
+
+    if errors.is_empty() { Ok(lints) } else { Err(errors) }
+}
+
+/// The values of the `#[clippy::...]` attributes of a lint declaration.
+#[derive(Default)]
+struct LintAttributes {
+    applicability: Option<String>,
+    default_level: Option<String>,
+}
+
+impl LintAttributes {
+    /// Sets the applicability of `lint` and overrides its inferred default level if given
+    fn apply_to(self, lint: &mut Lint) {
+        lint.applicability = self.applicability;
+        if let Some(level) = self.default_level {
+            lint.default_level = Some(level);
+        }
+    }
+}
+
+/// Consumes the description after a lint's group, i.e. `, "description"` with an optional trailing
+/// comma. Adjacent string literals, like `"part one " "part two"`, are all returned so they can be
+/// joined. Other literals aren't descriptions and end the list. Returns the literals and the first
+/// token after them.
+fn parse_desc_literals<'a>(
+    iter: &mut impl Iterator<Item = LintDeclSearchResult<'a>>,
+) -> (Vec<LintDeclSearchResult<'a>>, Option<LintDeclSearchResult<'a>>) {
+    let mut literals = Vec::new();
+    let mut next = iter.next();
+    if let Some(LintDeclSearchResult {
+        token_kind: TokenKind::Comma,
+        ..
+    }) = next
+    {
+        next = iter.next();
+        while let Some(
+            literal @ LintDeclSearchResult {
+                token_kind:
+                    TokenKind::Literal {
+                        kind: LiteralKind::Str { terminated: true } | LiteralKind::RawStr { n_hashes: Some(_) },
+                        ..
+                    },
+                ..
+            },
+        ) = next
+        {
+            literals.push(literal);
+            next = iter.next();
+        }
+        // optional trailing `,`
+        if !literals.is_empty()
+            && let Some(LintDeclSearchResult {
+                token_kind: TokenKind::Comma,
+                ..
+            }) = next
+        {
+            next = iter.next();
+        }
+    }
+    (literals, next)
+}
+
+/// Consumes the doc comments and attributes preceding a lint's name. Returns the doc comment lines,
+/// the values of the `#[clippy::applicability]` and `#[clippy::default_level]` attributes and the
+/// first token after them.
+fn parse_docs_and_attributes<'a>(
+    iter: &mut impl Iterator<Item = LintDeclSearchResult<'a>>,
+) -> (Vec<&'a str>, LintAttributes, Option<LintDeclSearchResult<'a>>) {
+    let mut docs = Vec::new();
+    let mut attrs = LintAttributes::default();
+    let mut next = iter.next();
+    loop {
+        match next {
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::LineComment { doc_style: Some(_) },
+                content,
+                ..
+            }) => docs.push(content),
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::Pound,
+                ..
+            }) => match parse_attribute(iter) {
+                Some(attr) => {
+                    if let Some(value) = clippy_attribute_value(&attr, "applicability") {
+                        attrs.applicability = Some(value);
+                    }
+                    if let Some(value) = clippy_attribute_value(&attr, "default_level") {
+                        attrs.default_level = Some(value.to_lowercase());
+                    }
+                },
+                None => break,
+            },
+            _ => break,
+        }
+        next = iter.next();
+    }
+    (docs, attrs, next)
+}
+
+/// Consumes the tokens of an attribute after its `#`, returning the tokens between the brackets.
+/// Returns `None` if the tokens don't form an attribute.
+fn parse_attribute<'a>(
+    iter: &mut impl Iterator<Item = LintDeclSearchResult<'a>>,
+) -> Option<Vec<LintDeclSearchResult<'a>>> {
+    if !matches!(iter.next(), Some(t) if t.token_kind == TokenKind::OpenBracket) {
+        return None;
+    }
+    let mut tokens = Vec::new();
+    let mut depth = 1;
+    for t in iter {
+        match t.token_kind {
+            TokenKind::OpenBracket => depth += 1,
+            TokenKind::CloseBracket => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(tokens);
+                }
+            },
+            _ => (),
+        }
+        tokens.push(t);
+    }
+    None
+}
+
+/// Returns the value of a `clippy::name = "value"` attribute, given the tokens between its brackets
+fn clippy_attribute_value(attr: &[LintDeclSearchResult<'_>], name: &str) -> Option<String> {
+    match attr {
+        [
+            LintDeclSearchResult {
+                token_kind: TokenKind::Ident,
+                content: "clippy",
+                ..
+            },
+            LintDeclSearchResult {
+                token_kind: TokenKind::Colon,
+                ..
+            },
+            LintDeclSearchResult {
+                token_kind: TokenKind::Colon,
+                ..
+            },
+            LintDeclSearchResult {
+                token_kind: TokenKind::Ident,
+                content: key,
+                ..
+            },
+            LintDeclSearchResult {
+                token_kind: TokenKind::Eq,
+                ..
+            },
+            LintDeclSearchResult {
+                token_kind:
+                    TokenKind::Literal {
+                        kind: LiteralKind::Str { terminated: true } | LiteralKind::RawStr { n_hashes: Some(_) },
+                        ..
+                    },
+                content: value,
+                ..
+            },
+        ] if *key == name => Some(remove_line_splices(value)),
+        _ => None,
+    }
+}
+
+/// Joins the lines of a doc comment, removing the leading `///` and the common indentation
+fn doc_comments_to_desc(docs: &[&str]) -> String {
+    let lines: Vec<_> = docs
+        .iter()
+        .map(|line| line.strip_prefix("///").unwrap_or(line).trim_end())
+        .collect();
+    let indent = lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or_default())
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Parse a source file looking for `declare_deprecated_lint` macro invocations.
+fn parse_deprecated_contents(contents: &str, lints: &mut Vec<DeprecatedLint>) {
+    let mut offset = 0usize;
+    let mut iter = tokenize(contents).map(|t| {
+        let range = offset..offset + t.len as usize;
+        offset = range.end;
+
+        LintDeclSearchResult {
+            token_kind: t.kind,
+            content: &contents[range.clone()],
+            range,
+        }
+    });
+
+    while let Some(LintDeclSearchResult { range, .. }) = iter.find(
+        |LintDeclSearchResult {
+             token_kind, content, ..
+         }| token_kind == &TokenKind::Ident && *content == "declare_deprecated_lint",
+    ) {
+        let start = range.start;
+
+        let mut iter = iter.by_ref().filter(|LintDeclSearchResult { ref token_kind, .. }| {
+            !matches!(token_kind, TokenKind::Whitespace | TokenKind::LineComment { .. })
+        });
+        let (name,) = match_tokens!(
+            iter,
+            // !{
+            Bang OpenBrace
+            // #[clippy::version = "version"]
+            Pound OpenBracket Ident Colon Colon Ident Eq Literal{..} CloseBracket
+            // pub LINT_NAME
+            Ident Ident(name)
+        );
+
+        // optional `=> NEW_NAME`
+        let replacement = match iter.next() {
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::Comma,
+                ..
+            }) => None,
+            Some(LintDeclSearchResult {
+                token_kind: TokenKind::Eq,
+                ..
+            }) => {
+                let (replacement,) = match_tokens!(iter, Gt Ident(replacement) Comma);
+                Some(replacement.to_lowercase())
+            },
+            _ => continue,
+        };
+
+        // "description"
+        let (reason,) = match_tokens!(
+            iter,
+            Literal {
+                kind: LiteralKind::Str { .. },
+                ..
+            }(reason)
+        );
+
+        if let Some(LintDeclSearchResult {
+            token_kind: TokenKind::CloseBrace,
+            range,
+            ..
+        }) = iter.next()
+        {
+            let mut lint = DeprecatedLint::new(name, reason, start..range.end);
+            lint.replacement = replacement;
+            lints.push(lint);
+        }
+    }
+}
+
+fn parse_renamed_contents(contents: &str, lints: &mut Vec<RenamedLint>) {
+    for line in contents.lines() {
+        let mut offset = 0usize;
+        let mut iter = tokenize(line).map(|t| {
+            let range = offset..offset + t.len as usize;
+            offset = range.end;
+
+            LintDeclSearchResult {
+                token_kind: t.kind,
+                content: &line[range.clone()],
+                range,
+            }
+        });
+
+        let (old_name, new_name) = match_tokens!(
+            iter,
+            // ("old_name",
+            Whitespace OpenParen Literal{kind: LiteralKind::Str{..},..}(old_name) Comma
+            // "new_name"),
+            Whitespace Literal{kind: LiteralKind::Str{..},..}(new_name) CloseParen Comma
+        );
+        lints.push(RenamedLint::new(old_name, new_name));
+    }
+}
+
+/// Parse a source file looking for `declare_lint_pass` and `impl_lint_pass` macro invocations,
+/// collecting the lowercased names of the lints registered by them.
+#[allow(clippy::implicit_hasher)]
+pub fn parse_lint_pass_contents(contents: &str, registered: &mut HashSet<String>) {
+    let mut offset = 0usize;
+    let mut iter = tokenize(contents)
+        .map(|t| {
+            let range = offset..offset + t.len as usize;
+            offset = range.end;
+
+            LintDeclSearchResult {
+                token_kind: t.kind,
+                content: &contents[range.clone()],
+                range,
+            }
+        })
+        .filter(|t| !matches!(t.token_kind, TokenKind::Whitespace | TokenKind::LineComment { .. }));
+
+    while iter.any(|t| t.token_kind == TokenKind::Ident && matches!(t.content, "declare_lint_pass" | "impl_lint_pass"))
+    {
+        // matches `!(PassName<'_> => [`
+        if !matches!(iter.next(), Some(t) if t.token_kind == TokenKind::Bang)
+            || !iter.any(|t| t.token_kind == TokenKind::OpenBracket)
+        {
+            continue;
+        }
+
+        // Only the last segment of a path (`module::LINT_NAME`) is the lint's name.
+        let mut name = None;
+        for t in iter.by_ref() {
+            match t.token_kind {
+                TokenKind::Ident => name = Some(t.content),
+                TokenKind::Comma | TokenKind::CloseBracket => {
+                    if let Some(name) = name.take() {
+                        registered.insert(name.to_lowercase());
+                    }
+                    if t.token_kind == TokenKind::CloseBracket {
+                        break;
+                    }
+                },
+                _ => (),
+            }
+        }
+    }
+}
+
+/// Parse the generated `declared_lints.rs` file, collecting the lowercased names of the lints whose
+/// `LintInfo` is listed in it.
+#[allow(clippy::implicit_hasher)]
+pub fn parse_declared_lints_contents(contents: &str, registered: &mut HashSet<String>) {
+    let mut offset = 0usize;
+    for t in tokenize(contents) {
+        let range = offset..offset + t.len as usize;
+        offset = range.end;
+
+        // Only the last segment of a path (`crate::module::LINT_NAME_INFO`) names the lint.
+        if t.kind == TokenKind::Ident
+            && let Some(name) = contents[range].strip_suffix("_INFO")
+        {
+            registered.insert(name.to_lowercase());
+        }
+    }
+}
+
+/// Removes the line splices and surrounding quotes from a string literal
+///
+/// The contents of raw string literals are returned verbatim.
+fn remove_line_splices(s: &str) -> String {
+    let (raw, s) = strip_quotes(s);
+    if raw {
+        return s.into();
+    }
+    let mut res = String::with_capacity(s.len());
+    unescape::unescape_literal(s, unescape::Mode::Str, &mut |range, ch| {
+        if ch.is_ok() {
+            res.push_str(&s[range]);
+        }
+    });
+    res
+}
+
+/// Removes the surrounding quotes from a string literal, returning whether it's a raw string
+fn strip_quotes(s: &str) -> (bool, &str) {
+    let (raw, unquoted) = match s.strip_prefix('r') {
+        Some(raw) => (true, raw.trim_matches('#')),
+        None => (false, s),
+    };
+    let s = unquoted
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .unwrap_or_else(|| panic!("expected quoted string, found `{s}`"));
+    (raw, s)
+}
+
+/// Replaces a region in a file delimited by two lines matching regexes.
+///
+/// `path` is the relative path to the file on which you want to perform the replacement.
+///
+/// See `replace_region_in_text` for documentation of the other options.
+///
+/// # Panics
+///
+/// Panics if the path could not read or then written
+#[cfg(feature = "fs")]
+fn replace_region_in_file(
+    update_mode: UpdateMode,
+    path: &Path,
+    start: &str,
+    end: &str,
+    write_replacement: impl FnMut(&mut String),
+) {
+    let contents = fs::read_to_string(path).unwrap_or_else(|e| panic!("Cannot read from `{}`: {e}", path.display()));
+    let new_contents = match replace_region_in_text(&contents, start, end, write_replacement) {
+        Ok(x) => x,
+        Err(delim) => panic!("Couldn't find `{delim}` in file `{}`", path.display()),
+    };
+
+    match update_mode {
+        UpdateMode::Check if contents != new_contents => exit_with_failure(),
+        UpdateMode::Check => (),
+        UpdateMode::Change => {
+            if let Err(e) = fs::write(path, new_contents.as_bytes()) {
+                panic!("Cannot write to `{}`: {e}", path.display());
+            }
+        },
+    }
+}
+
+/// Replaces a region in a text delimited by two strings. Returns the new text if both delimiters
+/// were found, or the missing delimiter if not.
+#[cfg(feature = "fs")]
+fn replace_region_in_text<'a>(
+    text: &str,
+    start: &'a str,
+    end: &'a str,
+    mut write_replacement: impl FnMut(&mut String),
+) -> Result<String, &'a str> {
+    let (text_start, rest) = text.split_once(start).ok_or(start)?;
+    let (_, text_end) = rest.split_once(end).ok_or(end)?;
+
+    let mut res = String::with_capacity(text.len() + 4096);
+    res.push_str(text_start);
+    res.push_str(start);
+    write_replacement(&mut res);
+    res.push_str(end);
+    res.push_str(text_end);
+
+    Ok(res)
+}
+
+#[cfg(feature = "fs")]
+fn try_rename_file(old_name: &Path, new_name: &Path) -> bool {
     match fs::OpenOptions::new().create_new(true).write(true).open(new_name) {
         Ok(file) => drop(file),
         Err(e) if matches!(e.kind(), io::ErrorKind::AlreadyExists | io::ErrorKind::NotFound) => return false,
@@ -1005,205 +3597,2237 @@ fn try_rename_file(old_name: &Path, new_name: &Path) -> bool {
             } else {
                 panic_file(e, old_name, "rename");
             }
-        },
-    }
-}
+        },
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+#[cfg(feature = "fs")]
+fn panic_file(error: io::Error, name: &Path, action: &str) -> ! {
+    panic!("failed to {action} file `{}`: {error}", name.display())
+}
+
+#[cfg(feature = "fs")]
+fn rewrite_file(path: &Path, f: impl FnOnce(&str) -> Option<String>) {
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .read(true)
+        .open(path)
+        .unwrap_or_else(|e| panic_file(e, path, "open"));
+    let mut buf = String::new();
+    file.read_to_string(&mut buf)
+        .unwrap_or_else(|e| panic_file(e, path, "read"));
+    if let Some(new_contents) = f(&buf) {
+        file.rewind().unwrap_or_else(|e| panic_file(e, path, "write"));
+        file.write_all(new_contents.as_bytes())
+            .unwrap_or_else(|e| panic_file(e, path, "write"));
+        file.set_len(new_contents.len() as u64)
+            .unwrap_or_else(|e| panic_file(e, path, "write"));
+    }
+}
+
+#[cfg(feature = "fs")]
+fn write_file(path: &Path, contents: &str) {
+    fs::write(path, contents).unwrap_or_else(|e| panic_file(e, path, "write"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_contents() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                #[clippy::version = "Hello Clippy!"]
+                pub PTR_ARG,
+                style,
+                "really long \
+                text"
+            }
+
+            declare_clippy_lint!{
+                #[clippy::version = "Test version"]
+                pub DOC_MARKDOWN,
+                pedantic,
+                "single line"
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+        assert_eq!(vec![2, 10], result.iter().map(|r| r.line).collect::<Vec<_>>());
+        assert_eq!(
+            vec!["\"really long \\\n                text\"", "\"single line\""],
+            result.iter().map(|r| &*r.raw_desc).collect::<Vec<_>>()
+        );
+        for r in &mut result {
+            r.declaration_range = Range::default();
+            r.line = 0;
+            r.raw_desc = format!("\"{}\"", r.desc);
+        }
+
+        let expected = vec![
+            Lint::new(
+                "ptr_arg",
+                "style",
+                "\"really long text\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new(
+                "doc_markdown",
+                "pedantic",
+                "\"single line\"",
+                "module_name",
+                Range::default(),
+            ),
+        ];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_parse_deprecated_contents() {
+        static DEPRECATED_CONTENTS: &str = r#"
+            /// some doc comment
+            declare_deprecated_lint! {
+                #[clippy::version = "I'm a version"]
+                pub SHOULD_ASSERT_EQ,
+                "`assert!()` will be more flexible with RFC 2011"
+            }
+        "#;
+
+        let mut result = Vec::new();
+        parse_deprecated_contents(DEPRECATED_CONTENTS, &mut result);
+        for r in &mut result {
+            r.declaration_range = Range::default();
+        }
+
+        let expected = vec![DeprecatedLint::new(
+            "should_assert_eq",
+            "\"`assert!()` will be more flexible with RFC 2011\"",
+            Range::default(),
+        )];
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn test_usable_lints() {
+        let lints = vec![
+            Lint::new(
+                "should_assert_eq2",
+                "Not Deprecated",
+                "\"abc\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new(
+                "should_assert_eq2",
+                "internal",
+                "\"abc\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new(
+                "should_assert_eq2",
+                "internal_style",
+                "\"abc\"",
+                "module_name",
+                Range::default(),
+            ),
+        ];
+        let expected = vec![Lint::new(
+            "should_assert_eq2",
+            "Not Deprecated",
+            "\"abc\"",
+            "module_name",
+            Range::default(),
+        )];
+        assert_eq!(expected, Lint::usable_lints(&lints));
+    }
+
+    #[test]
+    fn test_usable_lints_excluding() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new(
+                "missing_const_for_fn",
+                "nursery",
+                "\"abc\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+            Lint::new("lint_author", "internal_warn", "\"abc\"", "utils", Range::default()),
+            Lint::new("cargo_common_metadata", "cargo", "\"abc\"", "cargo", Range::default()),
+        ];
+        let exclude: HashSet<_> = ["nursery", "cargo"].into_iter().map(String::from).collect();
+        let usable: Vec<_> = Lint::usable_lints_excluding(lints.clone().into_iter(), &exclude)
+            .map(|l| l.name)
+            .collect();
+        assert_eq!(vec!["ptr_arg"], usable);
+
+        let exclude: HashSet<_> = ["nursery*"].into_iter().map(String::from).collect();
+        let usable: Vec<_> = Lint::usable_lints_excluding(lints.clone().into_iter(), &exclude)
+            .map(|l| l.name)
+            .collect();
+        assert_eq!(vec!["ptr_arg", "missing_const_for_fn", "cargo_common_metadata"], usable);
+
+        let usable: Vec<_> = Lint::usable_lints(&lints).into_iter().map(|l| l.name).collect();
+        assert_eq!(vec!["ptr_arg", "missing_const_for_fn", "cargo_common_metadata"], usable);
+    }
+
+    #[test]
+    fn test_all_internal_lints() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new(
+                "should_assert_eq",
+                "deprecated",
+                "\"abc\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+            Lint::new("lint_author", "internal_style", "\"abc\"", "utils", Range::default()),
+        ];
+        let internal: Vec<_> = Lint::all_internal_lints(lints.into_iter()).map(|l| l.name).collect();
+        assert_eq!(vec!["invalid_paths", "lint_author"], internal);
+    }
+
+    #[test]
+    fn test_lint_ord() {
+        let lints: BTreeSet<_> = [
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("cmp_null", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", 10..20),
+        ]
+        .into_iter()
+        .collect();
+        let lints: Vec<_> = lints.iter().map(|l| (&*l.module, &*l.name)).collect();
+        assert_eq!(
+            vec![
+                ("doc", "doc_markdown"),
+                ("ptr", "cmp_null"),
+                ("ptr", "ptr_arg"),
+                ("ptr", "ptr_arg")
+            ],
+            lints
+        );
+    }
+
+    #[test]
+    fn test_lint_builder() {
+        let lint = Lint::builder()
+            .name("PTR_ARG")
+            .group("style")
+            .desc("really long \\\n    text")
+            .module("ptr")
+            .declaration_range(10..20)
+            .build();
+        assert_eq!(
+            Lint::new("ptr_arg", "style", "\"really long \\\n    text\"", "ptr", 10..20),
+            lint
+        );
+        assert_eq!("really long text", lint.desc);
+    }
+
+    #[test]
+    fn test_by_lint_group() {
+        let lint = |name: &str, group: &str| {
+            Lint::builder()
+                .name(name)
+                .group(group)
+                .desc("abc")
+                .module("module_name")
+                .build()
+        };
+        let lints = vec![
+            lint("should_assert_eq", "group1"),
+            lint("should_assert_eq2", "group2"),
+            lint("incorrect_match", "group1"),
+        ];
+        let mut expected: HashMap<String, Vec<Lint>> = HashMap::new();
+        expected.insert(
+            "group1".to_string(),
+            vec![lint("should_assert_eq", "group1"), lint("incorrect_match", "group1")],
+        );
+        expected.insert("group2".to_string(), vec![lint("should_assert_eq2", "group2")]);
+        assert_eq!(expected, Lint::by_lint_group(lints.into_iter()));
+    }
+
+    #[test]
+    fn test_sorted_for_display() {
+        let lint = |name: &str, group: &str| {
+            Lint::builder()
+                .name(name)
+                .group(group)
+                .desc("abc")
+                .module("module_name")
+                .build()
+        };
+        let lints = vec![
+            Lint::builder()
+                .name("doc_markdown")
+                .group("pedantic")
+                .desc("abc")
+                .module("doc")
+                .build(),
+            Lint::builder()
+                .name("ptr_arg")
+                .group("style")
+                .desc("abc")
+                .module("ptr")
+                .build(),
+            lint("foo", "unknown_b"),
+            lint("bar", "unknown_a"),
+            lint("absurd_comparison", "correctness"),
+            Lint::builder()
+                .name("box_vec")
+                .group("style")
+                .desc("abc")
+                .module("types")
+                .build(),
+        ];
+        let names: Vec<_> = Lint::sorted_for_display(&lints).into_iter().map(|l| l.name).collect();
+        assert_eq!(
+            vec!["absurd_comparison", "box_vec", "ptr_arg", "doc_markdown", "bar", "foo"],
+            names
+        );
+    }
+
+    #[test]
+    fn test_group_normalization() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! { pub FOO, Style, "foo" }
+            declare_clippy_lint! { pub BAR, style, "bar" }
+            declare_clippy_lint! {
+                /// Docs
+                pub BAZ, STYLE
+            }
+        "#;
+        let mut lints = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut lints);
+
+        let groups = Lint::by_lint_group(lints.into_iter());
+        assert_eq!(vec!["style"], groups.keys().collect::<Vec<_>>());
+        assert_eq!(3, groups["style"].len());
+    }
+
+    #[test]
+    fn test_by_lint_group_sorted() {
+        let lint = |name: &str, group: &str| {
+            Lint::builder()
+                .name(name)
+                .group(group)
+                .desc("abc")
+                .module("module_name")
+                .build()
+        };
+        let lints = vec![
+            lint("should_assert_eq", "group2"),
+            lint("incorrect_match", "group1"),
+            lint("absurd_comparison", "group2"),
+        ];
+        let groups = Lint::by_lint_group_sorted(&lints);
+
+        assert_eq!(vec!["group1", "group2"], groups.keys().collect::<Vec<_>>());
+        assert_eq!(
+            vec!["absurd_comparison", "should_assert_eq"],
+            groups["group2"].iter().map(|l| l.name.as_str()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_by_lint_group_dedup() {
+        let lint = |name: &str, group: &str| {
+            Lint::builder()
+                .name(name)
+                .group(group)
+                .desc("abc")
+                .module("module_name")
+                .build()
+        };
+        let lints = vec![
+            lint("should_assert_eq", "group1"),
+            lint("incorrect_match", "group1"),
+            Lint::builder()
+                .name("should_assert_eq")
+                .group("group1")
+                .desc("def")
+                .module("other_module")
+                .build(),
+            lint("should_assert_eq", "group2"),
+        ];
+        let mut expected: HashMap<String, Vec<Lint>> = HashMap::new();
+        expected.insert(
+            "group1".to_string(),
+            vec![lint("incorrect_match", "group1"), lint("should_assert_eq", "group1")],
+        );
+        expected.insert("group2".to_string(), vec![lint("should_assert_eq", "group2")]);
+        assert_eq!(expected, Lint::by_lint_group_dedup(&lints));
+    }
+
+    #[test]
+    fn test_by_first_letter() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("box_vec", "perf", "\"abc\"", "types", Range::default()),
+            Lint::new(
+                "panic",
+                "restriction",
+                "\"abc\"",
+                "panic_unimplemented",
+                Range::default(),
+            ),
+            Lint::new("1st_lint", "style", "\"abc\"", "module_name", Range::default()),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+        ];
+        let letters: Vec<_> = Lint::by_first_letter(&lints)
+            .into_iter()
+            .map(|(letter, lints)| (letter, lints.into_iter().map(|l| l.name).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(
+            vec![
+                ('#', vec!["1st_lint".to_string()]),
+                ('B', vec!["box_vec".to_string()]),
+                ('P', vec!["panic".to_string(), "ptr_arg".to_string()]),
+            ],
+            letters
+        );
+    }
+
+    #[test]
+    fn test_deprecated_lint_reasons() {
+        let lints = vec![
+            DeprecatedLint::new("should_assert_eq", "\"`assert!()` is more flexible\"", Range::default()),
+            DeprecatedLint::new("extend_from_slice", "\"`.extend_from_slice(_)` is faster\"", 10..20),
+        ];
+        assert_eq!(
+            vec![
+                ("extend_from_slice", "`.extend_from_slice(_)` is faster"),
+                ("should_assert_eq", "`assert!()` is more flexible"),
+            ],
+            DeprecatedLint::reasons(&lints)
+        );
+    }
+
+    #[test]
+    fn test_parse_deprecated_contents_replacement() {
+        static CONTENTS: &str = r#"
+            declare_deprecated_lint! {
+                #[clippy::version = "pre 1.29.0"]
+                pub SHOULD_ASSERT_EQ,
+                "`assert!()` will be more flexible with RFC 2011"
+            }
+
+            declare_deprecated_lint! {
+                #[clippy::version = "1.70.0"]
+                pub OLD_LINT => NEW_LINT,
+                "`new_lint` checks for this too"
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_deprecated_contents(CONTENTS, &mut result);
+
+        assert_eq!(2, result.len());
+        assert_eq!("should_assert_eq", result[0].name);
+        assert_eq!(None, result[0].replacement);
+        assert_eq!("old_lint", result[1].name);
+        assert_eq!("`new_lint` checks for this too", result[1].reason);
+        assert_eq!(Some("new_lint"), result[1].replacement.as_deref());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_json_round_trip() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"really long text\"", "module_name", 10..20),
+            Lint::new("doc_markdown", "pedantic", "\"single line\"", "doc", Range::default()),
+        ];
+        let deprecated_lints = vec![DeprecatedLint::new(
+            "should_assert_eq",
+            "\"`assert!()` will be more flexible with RFC 2011\"",
+            Range::default(),
+        )];
+
+        assert_eq!(lints, lints_from_json::<Lint>(&lints_to_json(&lints)).unwrap());
+        assert!(lints_to_json(&lints).starts_with(r#"[{"id":"ptr_arg","name":"ptr_arg","#));
+        assert_eq!(
+            deprecated_lints,
+            lints_from_json::<DeprecatedLint>(&lints_to_json(&deprecated_lints)).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_render_lint_index_json() {
+        let lints = vec![
+            Lint::new(
+                "ptr_arg",
+                "style",
+                "\"really long text\"",
+                "module_name",
+                Range::default(),
+            ),
+            Lint::new("doc_markdown", "pedantic", "\"single line\"", "doc", Range::default()),
+        ];
+        let deprecated_lints = vec![DeprecatedLint::new(
+            "should_assert_eq",
+            "\"`assert!()` will be more flexible with RFC 2011\"",
+            Range::default(),
+        )];
+        let expected = r#"{
+  "doc_markdown": {
+    "desc": "single line",
+    "group": "pedantic",
+    "url": "https://rust-lang.github.io/rust-clippy/master/index.html#doc_markdown"
+  },
+  "ptr_arg": {
+    "desc": "really long text",
+    "group": "style",
+    "url": "https://rust-lang.github.io/rust-clippy/master/index.html#ptr_arg"
+  },
+  "should_assert_eq": {
+    "deprecation": "`assert!()` will be more flexible with RFC 2011",
+    "desc": "",
+    "group": "deprecated",
+    "url": "https://rust-lang.github.io/rust-clippy/master/index.html#should_assert_eq"
+  }
+}"#;
+        assert_eq!(expected, render_lint_index_json(&lints, &deprecated_lints));
+    }
+
+    #[test]
+    fn test_find_duplicates() {
+        let lints = vec![
+            Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("ptr_arg", "style", "\"abc\"", "methods", Range::default()),
+        ];
+        let expected = vec![("ptr_arg".to_string(), vec!["ptr".to_string(), "methods".to_string()])];
+        assert_eq!(expected, Lint::find_duplicates(&lints));
+    }
+
+    #[test]
+    fn test_terse_restriction_lints() {
+        let lints = vec![
+            Lint::new(
+                "as_conversions",
+                "restriction",
+                "\"using `as`\"",
+                "as_conversions",
+                Range::default(),
+            ),
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new(
+                "exit",
+                "restriction",
+                "\"detects calls to the `exit()` function which terminates the program\"",
+                "exit",
+                Range::default(),
+            ),
+            Lint::new(
+                "dbg_macro",
+                "restriction",
+                "\"`dbg!` macro is intended as a debugging tool\"",
+                "dbg_macro",
+                Range::default(),
+            ),
+        ];
+        let names = |min_words| -> Vec<_> {
+            Lint::terse_restriction_lints(&lints, min_words)
+                .into_iter()
+                .map(|l| l.name.clone())
+                .collect()
+        };
+        assert_eq!(vec!["as_conversions"], names(3));
+        assert_eq!(vec!["as_conversions", "dbg_macro"], names(9));
+        assert!(names(0).is_empty());
+    }
+
+    #[test]
+    fn test_whitespace_dirty_descriptions() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"a clean description\"", "ptr", Range::default()),
+            Lint::new("tab", "style", "\"a\tb\"", "module_name", Range::default()),
+            Lint::new("leading", "style", "\" leading\"", "module_name", Range::default()),
+            Lint::new("trailing", "style", "\"trailing \"", "module_name", Range::default()),
+            Lint::new("double", "style", "\"two  spaces\"", "module_name", Range::default()),
+            Lint::new("empty", "style", "\"\"", "module_name", Range::default()),
+        ];
+        let dirty: Vec<_> = Lint::whitespace_dirty_descriptions(&lints)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["tab", "leading", "trailing", "double"], dirty);
+    }
+
+    #[test]
+    fn test_duplicate_anchors() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("PTR_ARG", "pedantic", "\"abc\"", "methods", Range::default()),
+        ];
+        assert_eq!(vec!["ptr_arg".to_string()], Lint::duplicate_anchors(&lints));
+        assert!(Lint::duplicate_anchors(&lints[..2]).is_empty());
+    }
+
+    #[test]
+    fn test_near_duplicate_descriptions() {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        assert_eq!(3, levenshtein(&chars("kitten"), &chars("sitting")));
+        assert_eq!(0, levenshtein(&chars(""), &chars("")));
+
+        let lints = vec![
+            Lint::new(
+                "ptr_arg",
+                "style",
+                "\"checks for `&Vec` arguments\"",
+                "ptr",
+                Range::default(),
+            ),
+            Lint::new(
+                "ptr_args",
+                "style",
+                "\"Checks for `&Vec` argument\"",
+                "ptr",
+                Range::default(),
+            ),
+            Lint::new(
+                "box_vec",
+                "perf",
+                "\"usage of `Box<Vec<T>>`\"",
+                "types",
+                Range::default(),
+            ),
+            Lint::new(
+                "invalid_paths",
+                "internal",
+                "\"checks for `&Vec` arguments\"",
+                "utils",
+                Range::default(),
+            ),
+        ];
+        assert_eq!(
+            vec![("ptr_arg".to_string(), "ptr_args".to_string())],
+            Lint::near_duplicate_descriptions(&lints, 0.9)
+        );
+        assert_eq!(3, Lint::near_duplicate_descriptions(&lints, 0.0).len());
+    }
+
+    #[test]
+    fn test_parse_lint_pass_contents() {
+        static CONTENTS: &str = r"
+            declare_lint_pass!(PtrArg => [PTR_ARG]);
+
+            impl_lint_pass!(Dereferencing<'_> => [
+                EXPLICIT_DEREF_METHODS,
+                // a comment
+                crate::needless_borrow::NEEDLESS_BORROW,
+            ]);
+        ";
+        let mut registered = HashSet::new();
+        parse_lint_pass_contents(CONTENTS, &mut registered);
+
+        let expected: HashSet<String> = ["ptr_arg", "explicit_deref_methods", "needless_borrow"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(expected, registered);
+
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let expected = vec![Lint::new(
+            "doc_markdown",
+            "pedantic",
+            "\"abc\"",
+            "doc",
+            Range::default(),
+        )];
+        assert_eq!(expected, Lint::unregistered_lints(&lints, &registered));
+    }
+
+    #[test]
+    fn test_lint_category() {
+        let lint = Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default());
+        assert_eq!(LintCategory::Style, lint.category());
+
+        let lint = Lint::new("ptr_arg", "styl", "\"abc\"", "ptr", Range::default());
+        assert_eq!(LintCategory::Unknown("styl".into()), lint.category());
+        assert_eq!("styl", lint.category().as_str());
+    }
+
+    #[test]
+    fn test_group_urls() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("needless_borrow", "style", "\"abc\"", "dereference", Range::default()),
+        ];
+        let expected: BTreeMap<_, _> = [
+            ("pedantic".to_string(), format!("{DOCS_LINK}?groups=pedantic")),
+            ("style".to_string(), format!("{DOCS_LINK}?groups=style")),
+        ]
+        .into();
+        assert_eq!(expected, group_urls(&lints));
+    }
+
+    #[test]
+    fn test_render_lint_page() {
+        let lint = Lint::new(
+            "ptr_arg",
+            "style",
+            "\"fn arguments of the type `&Vec<...>`\"",
+            "ptr",
+            Range::default(),
+        );
+        let expected = format!(
+            "# ptr_arg\n\n`style`\n\nfn arguments of the type `&Vec<...>`\n\n[All style lints]({DOCS_LINK}?groups=style)\n"
+        );
+        assert_eq!(expected, render_lint_page(&lint));
+
+        let lint = DeprecatedLint::new(
+            "should_assert_eq",
+            "\"`assert!()` will be more flexible with RFC 2011\"",
+            Range::default(),
+        );
+        let expected = format!(
+            "# should_assert_eq\n\n`deprecated`\n\n> **Deprecated:** `assert!()` will be more flexible with RFC 2011\n\n[All lints]({DOCS_LINK})\n"
+        );
+        assert_eq!(expected, render_deprecated_lint_page(&lint));
+    }
+
+    #[test]
+    fn test_render_lint_table() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"a | b\"", "ptr", Range::default()),
+            Lint::new("internal_lint", "internal", "\"abc\"", "utils", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let expected = format!(
+            "| Lint | Group | Description |\n\
+            | --- | --- | --- |\n\
+            | [`doc_markdown`]({DOCS_LINK}#doc_markdown) | pedantic | abc |\n\
+            | [`ptr_arg`]({DOCS_LINK}#ptr_arg) | style | a \\| b |\n"
+        );
+        assert_eq!(expected, render_lint_table(&lints));
+    }
+
+    #[test]
+    fn test_render_deprecated_table() {
+        let mut lints = vec![
+            DeprecatedLint::new(
+                "unstable_as_slice",
+                "\"`Vec::as_slice` has been stabilized\"",
+                Range::default(),
+            ),
+            DeprecatedLint::new("should_assert_eq", "\"a | b\"", Range::default()),
+        ];
+        lints[0].replacement = Some("as_slice".into());
+        let expected = format!(
+            "| Lint | Reason | Replacement |\n\
+            | --- | --- | --- |\n\
+            | [`should_assert_eq`]({DOCS_LINK}#should_assert_eq) | a \\| b |  |\n\
+            | [`unstable_as_slice`]({DOCS_LINK}#unstable_as_slice) | `Vec::as_slice` has been stabilized | `as_slice` |\n"
+        );
+        assert_eq!(expected, render_deprecated_table(&lints));
+    }
+
+    #[test]
+    fn test_parse_contents_doc_comment_desc() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                /// Checks for `foo`.
+                ///
+                ///     indented
+                #[clippy::version = "1.0.0"]
+                pub FOO,
+                style
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+
+        assert_eq!(1, result.len());
+        assert_eq!("foo", result[0].name);
+        assert_eq!("Checks for `foo`.\n\n    indented", result[0].desc);
+    }
+
+    #[test]
+    fn test_url() {
+        let lint = Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default());
+        assert_eq!(format!("{DOCS_LINK}#ptr_arg"), lint.url());
+
+        let lint = DeprecatedLint::new("SHOULD_ASSERT_EQ", "\"abc\"", Range::default());
+        assert_eq!(format!("{DOCS_LINK}#should_assert_eq"), lint.url());
+
+        for base in [
+            "https://rust-lang.github.io/rust-clippy/rust-1.72.0/index.html",
+            "https://rust-lang.github.io/rust-clippy/rust-1.72.0/index.html/",
+        ] {
+            assert_eq!(
+                "https://rust-lang.github.io/rust-clippy/rust-1.72.0/index.html#should_assert_eq",
+                lint.url_with_base(base)
+            );
+        }
+    }
+
+    #[test]
+    fn test_url_scheme() {
+        let lint = Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default());
+        assert_eq!(lint.url(), lint.url_with(DOCS_LINK, UrlScheme::Name));
+        assert_eq!(
+            format!("{DOCS_LINK}#clippy::ptr_arg"),
+            lint.url_with(DOCS_LINK, UrlScheme::ToolPrefixed)
+        );
+
+        let lint = DeprecatedLint::new("SHOULD_ASSERT_EQ", "\"abc\"", Range::default());
+        assert_eq!(
+            format!("{DOCS_LINK}#should_assert_eq"),
+            lint.url_with(DOCS_LINK, UrlScheme::Name)
+        );
+        assert_eq!(
+            format!("{DOCS_LINK}#clippy::should_assert_eq"),
+            lint.url_with(DOCS_LINK, UrlScheme::ToolPrefixed)
+        );
+    }
+
+    #[test]
+    fn test_new_since() {
+        let lints = vec![
+            Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("DOC_MARKDOWN", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let known: HashSet<String> = ["ptr_arg".to_string()].into();
+        let new: Vec<_> = Lint::new_since(&lints, &known).map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["doc_markdown"], new);
+    }
+
+    #[test]
+    fn test_id() {
+        let mut lint = Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default());
+        assert_eq!("ptr_arg", lint.id());
+        lint.desc = "edited".into();
+        assert_eq!("ptr_arg", lint.id());
+    }
+
+    #[test]
+    fn test_desc_html() {
+        let lint = Lint::new(
+            "box_vec",
+            "perf",
+            "\"usage of `Box<Vec<T>>` & Vec<T>\"",
+            "types",
+            Range::default(),
+        );
+        assert_eq!(
+            "usage of <code>Box&lt;Vec&lt;T&gt;&gt;</code> &amp; Vec&lt;T&gt;",
+            lint.desc_html()
+        );
+        assert_eq!("usage of `Box<Vec<T>>` & Vec<T>", lint.desc);
+    }
+
+    #[test]
+    fn test_description_length_extremes() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abcdef\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"ab\"", "doc", Range::default()),
+            Lint::new("box_vec", "perf", "\"abc\"", "types", Range::default()),
+            Lint::new(
+                "needless_borrow",
+                "style",
+                "\"abcdef\"",
+                "dereference",
+                Range::default(),
+            ),
+            Lint::new("invalid_paths", "internal", "\"abcdefghi\"", "utils", Range::default()),
+            Lint::new("unit_arg", "complexity", "\"äöüä\"", "unit_types", Range::default()),
+        ];
+        let (longest, shortest) = Lint::description_length_extremes(&lints, 2);
+        let longest: Vec<_> = longest.into_iter().map(|l| l.name.as_str()).collect();
+        let shortest: Vec<_> = shortest.into_iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["needless_borrow", "ptr_arg"], longest);
+        assert_eq!(vec!["doc_markdown", "box_vec"], shortest);
+    }
+
+    #[test]
+    fn test_collisions_with_rustc() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("unused_unit", "style", "\"abc\"", "unused_unit", Range::default()),
+            Lint::new("dead_code", "style", "\"abc\"", "module_name", Range::default()),
+        ];
+        let rustc_names: HashSet<_> = ["DEAD_CODE", "unused_imports"].into_iter().map(String::from).collect();
+        let collisions: Vec<_> = Lint::collisions_with_rustc(&lints, &rustc_names)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["dead_code"], collisions);
+    }
+
+    #[test]
+    fn test_by_group_then_module() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("cmp_null", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_link", "style", "\"abc\"", "doc", Range::default()),
+        ];
+        let result = Lint::by_group_then_module(&lints);
+
+        assert_eq!(vec!["pedantic", "style"], result.keys().collect::<Vec<_>>());
+        assert_eq!(vec!["doc"], result["pedantic"].keys().collect::<Vec<_>>());
+        assert_eq!(vec!["doc", "ptr"], result["style"].keys().collect::<Vec<_>>());
+        assert_eq!(vec![lints[1].clone()], result["pedantic"]["doc"]);
+        assert_eq!(vec![lints[3].clone()], result["style"]["doc"]);
+        assert_eq!(vec![lints[2].clone(), lints[0].clone()], result["style"]["ptr"]);
+    }
+
+    #[test]
+    fn test_present_categories() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("foo", "styl", "\"abc\"", "module_name", Range::default()),
+            Lint::new("cmp_null", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("bar", "complexty", "\"abc\"", "module_name", Range::default()),
+            Lint::new("eq_op", "correctness", "\"abc\"", "operators", Range::default()),
+        ];
+        assert_eq!(
+            vec![
+                LintCategory::Correctness,
+                LintCategory::Style,
+                LintCategory::Pedantic,
+                LintCategory::Unknown("complexty".into()),
+                LintCategory::Unknown("styl".into()),
+            ],
+            Lint::present_categories(&lints)
+        );
+    }
+
+    #[test]
+    fn test_lints_with_unknown_group() {
+        let lints = vec![
+            Lint::new("ptr_arg", "styl", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new(
+                "needless_borrow",
+                "complexty",
+                "\"abc\"",
+                "dereference",
+                Range::default(),
+            ),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+        ];
+        let unknown: Vec<_> = Lint::lints_with_unknown_group(&lints)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["needless_borrow", "ptr_arg"], unknown);
+    }
+
+    #[test]
+    fn test_search() {
+        let lints = vec![
+            Lint::new(
+                "ptr_arg",
+                "style",
+                "\"fn arguments of the type `&Vec<...>`\"",
+                "ptr",
+                Range::default(),
+            ),
+            Lint::new(
+                "box_vec",
+                "perf",
+                "\"usage of `Box<Vec<T>>`\"",
+                "types",
+                Range::default(),
+            ),
+            Lint::new("useless_vec", "perf", "\"useless `vec!`\"", "vec", Range::default()),
+            Lint::new(
+                "doc_markdown",
+                "pedantic",
+                "\"presence of `_`\"",
+                "doc",
+                Range::default(),
+            ),
+        ];
+        let names = |query| {
+            Lint::search(&lints, query)
+                .into_iter()
+                .map(|l| l.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(vec!["box_vec", "useless_vec", "ptr_arg"], names("VEC"));
+        assert_eq!(vec!["ptr_arg"], names("arguments"));
+        assert!(names("nothing").is_empty());
+    }
+
+    #[test]
+    fn test_plain_desc() {
+        let lint = Lint::new(
+            "should_assert_eq",
+            "style",
+            "\"using `assert!()` **instead** of `*const T`\"",
+            "module_name",
+            Range::default(),
+        );
+        assert_eq!("using assert!() instead of *const T", lint.plain_desc());
+        assert_eq!("using `assert!()` **instead** of `*const T`", lint.desc);
+    }
+
+    #[test]
+    fn test_referenced_lints() {
+        let all: HashSet<String> = ["ptr_arg", "box_vec", "vec"].into_iter().map(String::from).collect();
+        let lint = Lint::new(
+            "box_vec",
+            "perf",
+            "\"like `clippy::ptr_arg` for boxes, see ptr_args, box_vec and PTR_ARG\"",
+            "types",
+            Range::default(),
+        );
+        assert_eq!(vec!["ptr_arg"], lint.referenced_lints(&all));
+    }
+
+    #[test]
+    fn test_lints_with_empty_desc() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("empty", "style", "\"\"", "ptr", Range::default()),
+            Lint::new("line_splice", "style", "\"\\\n   \"", "ptr", Range::default()),
+        ];
+        let result: Vec<_> = Lint::lints_with_empty_desc(&lints).iter().map(|l| &*l.name).collect();
+        assert_eq!(vec!["empty", "line_splice"], result);
+    }
+
+    #[test]
+    fn test_group_counts() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("needless_borrow", "style", "\"abc\"", "dereference", Range::default()),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+            Lint::new(
+                "compiler_lint_functions",
+                "internal_warn",
+                "\"abc\"",
+                "utils",
+                Range::default(),
+            ),
+        ];
+        let expected: BTreeMap<_, _> = [("pedantic".to_string(), 1), ("style".to_string(), 2)].into();
+        assert_eq!(expected, Lint::group_counts(&lints));
+        assert_eq!(3, Lint::total_usable(&lints));
+    }
+
+    #[test]
+    fn test_module_counts() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("cmp_null", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let deprecated = vec![DeprecatedLint::new(
+            "should_assert_eq",
+            "\"has been superseded by `should_assert_eq2`\"",
+            Range::default(),
+        )];
+        let expected: BTreeMap<_, _> = [
+            ("deprecated_lints".to_string(), 1),
+            ("doc".to_string(), 1),
+            ("ptr".to_string(), 2),
+        ]
+        .into();
+        assert_eq!(expected, Lint::module_counts(&lints, &deprecated));
+        assert_eq!(
+            vec!["deprecated_lints", "doc", "ptr"],
+            Lint::modules(&lints, &deprecated).iter().collect::<Vec<_>>()
+        );
+        assert_eq!(2, Lint::modules(&lints, &[]).len());
+    }
+
+    #[test]
+    fn test_description_word_frequencies() {
+        let lints = vec![
+            Lint::new(
+                "box_vec",
+                "perf",
+                "\"usage of `Box<Vec<T>>`, vec in a box\"",
+                "types",
+                Range::default(),
+            ),
+            Lint::new("useless_vec", "perf", "\"useless `vec!`\"", "vec", Range::default()),
+            Lint::new(
+                "invalid_paths",
+                "internal",
+                "\"invalid vec path\"",
+                "utils",
+                Range::default(),
+            ),
+        ];
+        let expected: BTreeMap<_, _> = [
+            ("a", 1),
+            ("box", 1),
+            ("in", 1),
+            ("of", 1),
+            ("t", 1),
+            ("usage", 1),
+            ("useless", 1),
+            ("vec", 2),
+        ]
+        .into_iter()
+        .map(|(w, c)| (w.to_string(), c))
+        .collect();
+        assert_eq!(expected, Lint::description_word_frequencies(&lints));
+    }
+
+    #[test]
+    fn test_render_snapshot() {
+        let mut ptr_arg = Lint::new("ptr_arg", "style", "\"really long\ntext\"", "ptr", 10..20);
+        ptr_arg.line = 3;
+        let lints = vec![
+            ptr_arg,
+            Lint::new(
+                "box_vec",
+                "perf",
+                "\"usage of `Box<Vec<T>>`\"",
+                "types",
+                Range::default(),
+            ),
+            Lint::new(
+                "as_conversions",
+                "restriction",
+                "\"abc\"",
+                "as_conversions",
+                Range::default(),
+            ),
+        ];
+        let expected = [
+            r#"as_conversions "as_conversions" group="restriction" desc="abc" raw_desc="\"abc\"" range=0..0 line=0 applicability=None default_level=Some("allow")"#,
+            r#"ptr "ptr_arg" group="style" desc="really long\ntext" raw_desc="\"really long\ntext\"" range=10..20 line=3 applicability=None default_level=Some("warn")"#,
+            r#"types "box_vec" group="perf" desc="usage of `Box<Vec<T>>`" raw_desc="\"usage of `Box<Vec<T>>`\"" range=0..0 line=0 applicability=None default_level=Some("warn")"#,
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, render_snapshot(&lints));
+    }
+
+    #[test]
+    fn test_lints_hash() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let reversed: Vec<_> = lints.iter().rev().cloned().collect();
+        assert_eq!(lints_hash(&lints), lints_hash(&reversed));
+        assert_eq!(0xcbf2_9ce4_8422_2325, lints_hash(&[]));
+
+        let mut changed = lints.clone();
+        changed[0].line = 1;
+        assert_ne!(lints_hash(&lints), lints_hash(&changed));
+    }
+
+    #[test]
+    fn test_verify_lint_count() {
+        let lints: Vec<_> = (0..1060)
+            .map(|i| {
+                Lint::new(
+                    &format!("lint_{i}"),
+                    "style",
+                    "\"abc\"",
+                    "module_name",
+                    Range::default(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            Ok(()),
+            verify_lint_count("[There are over 1,050 lints included in this crate!](link)", &lints)
+        );
+        assert_eq!(
+            Err(CountMismatch {
+                readme: Some(1000),
+                expected: 1050,
+            }),
+            verify_lint_count("[There are over 1000 lints included in this crate!](link)", &lints)
+        );
+        assert_eq!(
+            Err(CountMismatch {
+                readme: None,
+                expected: 1050,
+            }),
+            verify_lint_count("There are many lints", &lints)
+        );
+    }
+
+    #[test]
+    fn test_redundant_descriptions() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"ptr arg\"", "ptr", Range::default()),
+            Lint::new("box_vec", "perf", "\"Box  Vec\"", "types", Range::default()),
+            Lint::new("useless_vec", "perf", "\"useless_vec\"", "vec", Range::default()),
+            Lint::new(
+                "doc_markdown",
+                "pedantic",
+                "\"presence of `_`\"",
+                "doc",
+                Range::default(),
+            ),
+        ];
+        let redundant: Vec<_> = Lint::redundant_descriptions(&lints)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["ptr_arg", "box_vec", "useless_vec"], redundant);
+    }
+
+    #[test]
+    fn test_lint_desc_style_violations() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"fine\"", "ptr", Range::default()),
+            Lint::new("uppercase", "style", "\"Uppercase\"", "ptr", Range::default()),
+            Lint::new("period", "style", "\"period.\"", "ptr", Range::default()),
+        ];
+        let result: Vec<_> = Lint::lint_desc_style_violations(&lints)
+            .into_iter()
+            .map(|(l, reason)| (&*l.name, reason))
+            .collect();
+        assert_eq!(
+            vec![
+                ("uppercase", "description starts with an uppercase letter".to_string()),
+                ("period", "description ends with a period".to_string()),
+            ],
+            result
+        );
+    }
+
+    #[test]
+    fn test_parse_contents_raw_string_desc() {
+        static CONTENTS: &str = r##"
+            declare_clippy_lint! {
+                #[clippy::version = "1.0.0"]
+                pub QUOTES,
+                style,
+                r#"contains "quotes" and a \
+                backslash"#
+            }
+        "##;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+
+        assert_eq!(1, result.len());
+        assert_eq!(
+            "contains \"quotes\" and a \\\n                backslash",
+            result[0].desc
+        );
+    }
+
+    #[test]
+    fn test_lint_set() {
+        let lints: LintSet = [
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("needless_borrow", "style", "\"abc\"", "dereference", Range::default()),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(4, lints.len());
+        assert_eq!(Some("doc"), lints.get("doc_markdown").map(|l| &*l.module));
+        assert!(lints.get("missing").is_none());
+
+        let by_group = lints.by_group();
+        assert_eq!(2, by_group["style"].len());
+        assert_eq!(1, by_group["pedantic"].len());
+
+        let style: Vec<_> = lints.in_group("style").map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["ptr_arg", "needless_borrow"], style);
+        assert_eq!(3, lints.usable().count());
+    }
+
+    #[test]
+    fn test_diff_lints() {
+        let old = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("old_name", "style", "\"abc\"", "module_name", Range::default()),
+        ];
+        let new = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "style", "\"abc\"", "doc", Range::default()),
+            Lint::new("new_name", "style", "\"abc\"", "module_name", Range::default()),
+        ];
+
+        let expected = LintDiff {
+            added: vec![new[2].clone()],
+            removed: vec![old[2].clone()],
+            changed: vec![(old[1].clone(), new[1].clone())],
+            moved: Vec::new(),
+        };
+        assert_eq!(expected, diff_lints(&old, &new));
+    }
+
+    #[test]
+    fn test_diff_lints_moved() {
+        let old = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("map_flatten", "complexity", "\"abc\"", "map_flatten", Range::default()),
+            Lint::new("filter_map", "style", "\"abc\"", "filter_map", Range::default()),
+        ];
+        let new = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new(
+                "map_flatten",
+                "complexity",
+                "\"abc\"",
+                "methods::map_flatten",
+                Range::default(),
+            ),
+            Lint::new(
+                "filter_map",
+                "style",
+                "\"def\"",
+                "methods::filter_map",
+                Range::default(),
+            ),
+        ];
+
+        let expected = LintDiff {
+            changed: vec![(old[2].clone(), new[2].clone())],
+            ..LintDiff::default()
+        };
+        assert_eq!(expected, diff_lints(&old, &new));
+
+        let expected = LintDiff {
+            changed: vec![(old[2].clone(), new[2].clone())],
+            moved: vec![(old[1].clone(), new[1].clone())],
+            ..LintDiff::default()
+        };
+        assert_eq!(
+            expected,
+            diff_lints_with(&old, &new, DiffOptions { ignore_module: true })
+        );
+    }
+
+    #[test]
+    fn test_additions_by_module() {
+        let old = vec![Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default())];
+        let new = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("ptr_eq", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("ptr_as_ptr", "pedantic", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let mut diff = diff_lints(&old, &new);
+        diff.removed
+            .push(Lint::new("old_name", "style", "\"abc\"", "doc", Range::default()));
+
+        let expected: BTreeMap<_, _> = [("doc".to_string(), 1), ("ptr".to_string(), 2)].into_iter().collect();
+        assert_eq!(expected, additions_by_module(&diff));
+    }
+
+    #[test]
+    fn test_lints_in_unexpected_module() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "methods", Range::default()),
+            Lint::new("needless_borrow", "style", "\"abc\"", "dereference", Range::default()),
+        ];
+        let expected: HashMap<_, _> = [("ptr_arg", "ptr"), ("doc_markdown", "doc")]
+            .into_iter()
+            .map(|(name, module)| (name.to_string(), module.to_string()))
+            .collect();
+        assert_eq!(vec![&lints[1]], Lint::lints_in_unexpected_module(&lints, &expected));
+    }
+
+    #[test]
+    fn test_lints_to_csv() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"a, b\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+        ];
+        let deprecated_lints = vec![DeprecatedLint::new(
+            "should_assert_eq",
+            "\"superseded, by `assert_eq`\"",
+            Range::default(),
+        )];
+        let expected = "name,group,desc,deprecation,module\n\
+            ptr_arg,style,\"a, b\",,ptr\n\
+            doc_markdown,pedantic,abc,,doc\n\
+            should_assert_eq,deprecated,,\"superseded, by `assert_eq`\",deprecated_lints\n";
+        assert_eq!(expected, lints_to_csv(&lints, &deprecated_lints));
+    }
+
+    #[test]
+    fn test_new_with_opts() {
+        let desc = "\"really \\\"long\\\" \\\n    text\"";
+        let normalized = Lint::new_with_opts("ptr_arg", "style", desc, "ptr", Range::default(), true);
+        assert_eq!("really \\\"long\\\" text", normalized.desc);
+        assert_eq!(Lint::new("ptr_arg", "style", desc, "ptr", Range::default()), normalized);
+
+        let kept = Lint::new_with_opts("ptr_arg", "style", desc, "ptr", Range::default(), false);
+        assert_eq!("really \\\"long\\\" \\\n    text", kept.desc);
+        assert_eq!(desc, kept.raw_desc);
+    }
+
+    #[test]
+    fn test_raw_desc() {
+        let lint = Lint::new(
+            "ptr_arg",
+            "style",
+            "\"really long \\\n    text\"",
+            "ptr",
+            Range::default(),
+        );
+        assert_eq!("really long text", lint.desc);
+        assert_eq!("\"really long \\\n    text\"", lint.raw_desc);
+    }
+
+    #[test]
+    fn test_parse_contents_attributes_and_comments() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                /// Docs
+                #[clippy::version = "1.0.0"]
+                #[cfg_attr(feature = "internal", allow(clippy::some_lint))]
+                // a comment
+                /* a block comment */
+                pub FOO,
+                style,
+                "foo"
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+
+        assert_eq!(1, result.len());
+        assert_eq!("foo", result[0].name);
+        assert_eq!("foo", result[0].desc);
+    }
+
+    #[test]
+    fn test_parse_contents_consecutive() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                pub FOO,
+                style,
+                "foo"
+            }
+            declare_clippy_lint! {
+                pub BAR,
+                style,
+                "bar"
+            }declare_clippy_lint! { pub BAZ, style, "baz" }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+
+        let names: Vec<_> = result.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["foo", "bar", "baz"], names);
+    }
+
+    #[test]
+    fn test_parse_contents_delimiters() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! { pub BRACES, style, "braces" }
+            declare_clippy_lint! { pub BRACES_COMMA, style, "braces comma", }
+            declare_clippy_lint!(pub PARENS, style, "parens");
+            declare_clippy_lint!(
+                pub PARENS_COMMA,
+                style,
+                "parens comma",
+            );
+            declare_clippy_lint! { pub MISMATCHED, style, "mismatched" );
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+
+        let lints: Vec<_> = result.iter().map(|l| (l.name.as_str(), l.desc.as_str())).collect();
+        assert_eq!(
+            vec![
+                ("braces", "braces"),
+                ("braces_comma", "braces comma"),
+                ("parens", "parens"),
+                ("parens_comma", "parens comma"),
+            ],
+            lints
+        );
+    }
+
+    #[test]
+    fn test_parse_with_macro() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! { pub PTR_ARG, style, "ptr arg" }
+
+            declare_tool_lint! {
+                /// ### What it does
+                pub CUSTOM_LINT,
+                pedantic,
+                "custom \
+                 lint"
+            }
+        "#;
+        let lints = parse_with_macro(CONTENTS, "module_name", "declare_tool_lint").unwrap();
+        assert_eq!(1, lints.len());
+        assert_eq!("custom_lint", lints[0].name);
+        assert_eq!("custom lint", lints[0].desc);
+        assert_eq!("pedantic", lints[0].group);
+
+        for name in ["declare_tool_lint!", "", "declare tool lint"] {
+            assert_eq!(
+                Err(InvalidMacroName(name.into())),
+                parse_with_macro(CONTENTS, "module_name", name)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_contents_concatenated_desc() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                pub PTR_ARG,
+                style,
+                "part one " "part two",
+            }
+
+            declare_clippy_lint! {
+                pub NOT_A_STRING,
+                style,
+                "part one " 2,
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+        assert_eq!(1, result.len());
+        assert_eq!("part one part two", result[0].desc);
+        assert_eq!(r#""part one " "part two""#, result[0].raw_desc);
+    }
+
+    #[test]
+    fn test_parse_contents_commented_out() {
+        static CONTENTS: &str = r#"
+            // see declare_clippy_lint! for details
+            // declare_clippy_lint! { pub LINE_COMMENT, style, "line comment" }
+
+            /*
+            declare_clippy_lint! {
+                pub BLOCK_COMMENT,
+                style,
+                "block comment /* nested */"
+            }
+            */
+
+            const DOCS: &str = "declare_clippy_lint! { pub STRING, style, \"string\" }";
+
+            declare_clippy_lint! {
+                pub PTR_ARG,
+                style,
+                "really long text"
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+        let names: Vec<_> = result.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(vec!["ptr_arg"], names);
+    }
+
+    #[test]
+    fn test_parse_contents_default_level() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                #[clippy::version = "1.70.0"]
+                pub AS_CONVERSIONS,
+                restriction,
+                "using a potentially dangerous silent `as` conversion"
+            }
 
-#[allow(clippy::needless_pass_by_value)]
-fn panic_file(error: io::Error, name: &Path, action: &str) -> ! {
-    panic!("failed to {action} file `{}`: {error}", name.display())
-}
+            declare_clippy_lint! {
+                #[clippy::default_level = "Warn"]
+                pub NEW_LINT,
+                nursery,
+                "new lint"
+            }
 
-fn rewrite_file(path: &Path, f: impl FnOnce(&str) -> Option<String>) {
-    let mut file = fs::OpenOptions::new()
-        .write(true)
-        .read(true)
-        .open(path)
-        .unwrap_or_else(|e| panic_file(e, path, "open"));
-    let mut buf = String::new();
-    file.read_to_string(&mut buf)
-        .unwrap_or_else(|e| panic_file(e, path, "read"));
-    if let Some(new_contents) = f(&buf) {
-        file.rewind().unwrap_or_else(|e| panic_file(e, path, "write"));
-        file.write_all(new_contents.as_bytes())
-            .unwrap_or_else(|e| panic_file(e, path, "write"));
-        file.set_len(new_contents.len() as u64)
-            .unwrap_or_else(|e| panic_file(e, path, "write"));
+            declare_clippy_lint! {
+                pub BOX_VEC,
+                perf,
+                "usage of `Box<Vec<T>>`"
+            }
+        "#;
+        let mut result = Vec::new();
+        parse_contents(CONTENTS, "module_name", &mut result);
+        assert_eq!(Some("allow"), result[0].default_level.as_deref());
+        assert_eq!(Some("warn"), result[1].default_level.as_deref());
+        assert_eq!(Some("warn"), result[2].default_level.as_deref());
+        let unknown = Lint::new("foo", "styl", "\"abc\"", "module_name", Range::default());
+        assert_eq!(None, unknown.default_level);
     }
-}
-
-fn write_file(path: &Path, contents: &str) {
-    fs::write(path, contents).unwrap_or_else(|e| panic_file(e, path, "write"));
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_parse_contents() {
+    fn test_parse_contents_applicability() {
         static CONTENTS: &str = r#"
             declare_clippy_lint! {
-                #[clippy::version = "Hello Clippy!"]
-                pub PTR_ARG,
+                #[clippy::version = "1.0.0"]
+                #[clippy::applicability = "MachineApplicable"]
+                pub FOO,
                 style,
-                "really long \
-                text"
+                "foo"
             }
 
-            declare_clippy_lint!{
-                #[clippy::version = "Test version"]
-                pub DOC_MARKDOWN,
-                pedantic,
-                "single line"
+            declare_clippy_lint! {
+                #[clippy::version = "1.0.0"]
+                pub BAR,
+                style,
+                "bar"
+            }
+
+            declare_clippy_lint! {
+                #[clippy::version = "1.0.0"]
+                #[clippy::applicability = 1]
+                pub BAZ,
+                style,
+                "baz"
             }
         "#;
         let mut result = Vec::new();
         parse_contents(CONTENTS, "module_name", &mut result);
-        for r in &mut result {
-            r.declaration_range = Range::default();
-        }
 
-        let expected = vec![
-            Lint::new(
-                "ptr_arg",
-                "style",
-                "\"really long text\"",
-                "module_name",
-                Range::default(),
-            ),
-            Lint::new(
-                "doc_markdown",
-                "pedantic",
-                "\"single line\"",
-                "module_name",
-                Range::default(),
-            ),
-        ];
-        assert_eq!(expected, result);
+        assert_eq!(3, result.len());
+        assert_eq!(Some("MachineApplicable"), result[0].applicability.as_deref());
+        assert_eq!(None, result[1].applicability);
+        assert_eq!(None, result[2].applicability);
     }
 
     #[test]
-    fn test_parse_deprecated_contents() {
-        static DEPRECATED_CONTENTS: &str = r#"
-            /// some doc comment
+    fn test_gather_from_reader() {
+        let source = "\u{feff}declare_clippy_lint! { pub FOO, style, \"foo\" }";
+        let lints = gather_from_reader(source.as_bytes(), "foo").unwrap();
+        assert_eq!(1, lints.len());
+        assert_eq!(gather_from_sources([("foo", &source[3..])]), lints);
+
+        let error = gather_from_reader(&[b'/', b'/', 0xff][..], "foo").unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+    }
+
+    #[test]
+    fn test_parse_in_source_order() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! { pub PTR_ARG, style, "ptr arg" }
             declare_deprecated_lint! {
-                #[clippy::version = "I'm a version"]
+                #[clippy::version = "pre 1.29.0"]
                 pub SHOULD_ASSERT_EQ,
-                "`assert!()` will be more flexible with RFC 2011"
+                "assert_eq"
             }
+            declare_clippy_lint! { pub BOX_VEC, perf, "box vec" }
         "#;
+        let declarations = parse_in_source_order(CONTENTS, "module_name");
+        let names: Vec<_> = declarations.iter().map(LintDeclaration::name).collect();
+        assert_eq!(vec!["ptr_arg", "should_assert_eq", "box_vec"], names);
+        assert!(matches!(declarations[1], LintDeclaration::Deprecated(_)));
+    }
 
-        let mut result = Vec::new();
-        parse_deprecated_contents(DEPRECATED_CONTENTS, &mut result);
-        for r in &mut result {
-            r.declaration_range = Range::default();
-        }
+    #[test]
+    fn test_lint_predicates() {
+        let lint = Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default());
+        assert!(lint.is_usable());
+        assert!(!lint.is_internal());
+        assert!(!lint.is_deprecated());
 
-        let expected = vec![DeprecatedLint::new(
+        let lint = Lint::new(
             "should_assert_eq",
-            "\"`assert!()` will be more flexible with RFC 2011\"",
+            "deprecated",
+            "\"abc\"",
+            "deprecated",
             Range::default(),
-        )];
-        assert_eq!(expected, result);
+        );
+        assert!(lint.is_deprecated());
+
+        for group in ["internal", "internal_warn"] {
+            let lint = Lint::new("invalid_paths", group, "\"abc\"", "utils", Range::default());
+            assert!(!lint.is_usable());
+            assert!(lint.is_internal());
+            assert!(!lint.is_deprecated());
+        }
     }
 
     #[test]
-    fn test_usable_lints() {
+    fn test_assert_unique_names() {
+        let mut lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("ptr_arg", "pedantic", "\"abc\"", "methods", Range::default()),
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+        ];
+        lints[1].name = "DOC_MARKDOWN".into();
+        lints.push(Lint::new("doc_markdown", "style", "\"abc\"", "doc", Range::default()));
+        assert_eq!(
+            Err(vec!["doc_markdown".to_string(), "ptr_arg".to_string()]),
+            Lint::assert_unique_names(&lints)
+        );
+    }
+
+    #[test]
+    fn test_parse_declared_lints_contents() {
+        static CONTENTS: &str = r"
+            pub(crate) static LINTS: &[&crate::LintInfo] = &[
+                #[cfg(feature = 'internal')]
+                crate::utils::internal_lints::invalid_paths::INVALID_PATHS_INFO,
+                crate::ptr::PTR_ARG_INFO,
+            ];
+        ";
+        let mut registered = HashSet::new();
+        parse_declared_lints_contents(CONTENTS, &mut registered);
+
+        let expected: HashSet<String> = ["invalid_paths", "ptr_arg"].into_iter().map(String::from).collect();
+        assert_eq!(expected, registered);
+
         let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
             Lint::new(
-                "should_assert_eq2",
-                "Not Deprecated",
-                "\"abc\"",
-                "module_name",
-                Range::default(),
-            ),
-            Lint::new(
-                "should_assert_eq2",
-                "internal",
+                "dump_hir",
+                "internal_warn",
                 "\"abc\"",
-                "module_name",
+                "utils::dump_hir",
                 Range::default(),
             ),
+        ];
+        let missing: Vec<_> = Lint::lints_missing_group_registration(&lints, &registered)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["doc_markdown"], missing);
+    }
+
+    #[test]
+    fn test_inconsistent_deprecation() {
+        let lints = vec![
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
             Lint::new(
-                "should_assert_eq2",
-                "internal_style",
+                "should_assert_eq",
+                "deprecated",
                 "\"abc\"",
                 "module_name",
                 Range::default(),
             ),
+            Lint::new("unstable_as_slice", "style", "\"abc\"", "module_name", Range::default()),
         ];
-        let expected = vec![Lint::new(
-            "should_assert_eq2",
-            "Not Deprecated",
-            "\"abc\"",
-            "module_name",
+        let deprecated = vec![DeprecatedLint::new(
+            "unstable_as_slice",
+            "\"`Vec::as_slice` has been stabilized\"",
             Range::default(),
         )];
-        assert_eq!(expected, Lint::usable_lints(&lints));
+        let inconsistent: Vec<_> = Lint::inconsistent_deprecation(&lints, &deprecated)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["should_assert_eq", "unstable_as_slice"], inconsistent);
     }
 
     #[test]
-    fn test_by_lint_group() {
+    fn test_invalid_lint_names() {
+        let long_name = "a".repeat(MAX_LINT_NAME_LEN + 1);
         let lints = vec![
-            Lint::new("should_assert_eq", "group1", "\"abc\"", "module_name", Range::default()),
-            Lint::new(
-                "should_assert_eq2",
-                "group2",
-                "\"abc\"",
-                "module_name",
-                Range::default(),
-            ),
-            Lint::new("incorrect_match", "group1", "\"abc\"", "module_name", Range::default()),
+            Lint::new("PTR_ARG", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("_ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("1ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("ptr-arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("ptr_arg2", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new(&long_name, "style", "\"abc\"", "ptr", Range::default()),
         ];
-        let mut expected: HashMap<String, Vec<Lint>> = HashMap::new();
-        expected.insert(
-            "group1".to_string(),
-            vec![
-                Lint::new("should_assert_eq", "group1", "\"abc\"", "module_name", Range::default()),
-                Lint::new("incorrect_match", "group1", "\"abc\"", "module_name", Range::default()),
-            ],
-        );
-        expected.insert(
-            "group2".to_string(),
-            vec![Lint::new(
-                "should_assert_eq2",
-                "group2",
-                "\"abc\"",
-                "module_name",
-                Range::default(),
-            )],
-        );
-        assert_eq!(expected, Lint::by_lint_group(lints.into_iter()));
+        let invalid: Vec<_> = Lint::invalid_lint_names(&lints)
+            .into_iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(vec!["_ptr_arg", "1ptr_arg", "ptr-arg", &long_name], invalid);
     }
 
     #[test]
-    fn test_gen_deprecated() {
+    fn test_compute_stats() {
         let lints = vec![
-            DeprecatedLint::new(
-                "should_assert_eq",
-                "\"has been superseded by should_assert_eq2\"",
-                Range::default(),
-            ),
-            DeprecatedLint::new("another_deprecated", "\"will be removed\"", Range::default()),
+            Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+            Lint::new("doc_markdown", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("missing_panics_doc", "pedantic", "\"abc\"", "doc", Range::default()),
+            Lint::new("invalid_paths", "internal", "\"abc\"", "utils", Range::default()),
         ];
+        let deprecated_lints = vec![DeprecatedLint::new("should_assert_eq", "\"abc\"", Range::default())];
+        let stats = Lint::compute_stats(&lints, &deprecated_lints);
+
+        assert_eq!(4, stats.total);
+        assert_eq!(3, stats.usable);
+        assert_eq!(1, stats.deprecated);
+        assert_eq!(1, stats.internal);
+        assert_eq!(Lint::group_counts(&lints), stats.group_counts);
+        assert_eq!(Lint::modules(&lints, &deprecated_lints).len(), stats.module_count);
+    }
+
+    #[test]
+    fn test_parse_contents_strict() {
+        static CONTENTS: &str = r#"
+            declare_clippy_lint! {
+                pub FOO,
+                style,
+                "foo"
+            }
+
+            declare_clippy_lint! {
+                pub BAR
+                style,
+                "bar"
+            }
+
+            declare_clippy_lint! { pub BAZ, "baz" }
+        "#;
+        let missing_comma = CONTENTS
+            .find("declare_clippy_lint! {\n                pub BAR")
+            .unwrap();
+        let missing_comma_end = missing_comma + CONTENTS[missing_comma..].find('}').unwrap() + 1;
+        let missing_group = CONTENTS.find("declare_clippy_lint! { pub BAZ").unwrap();
+        assert_eq!(
+            Err(vec![
+                ParseError {
+                    module: "module_name".into(),
+                    line: 8,
+                    span: missing_comma..missing_comma_end,
+                },
+                ParseError {
+                    module: "module_name".into(),
+                    line: 14,
+                    span: missing_group..missing_group + "declare_clippy_lint! { pub BAZ, \"baz\" }".len(),
+                },
+            ]),
+            parse_contents_strict(CONTENTS, "module_name")
+        );
+
+        let lints = parse_contents_strict("declare_clippy_lint! { pub FOO, style, \"foo\" }", "module_name").unwrap();
+        assert_eq!(1, lints.len());
+
+        let contents = "declare_clippy_lint! { pub FOO, style, \"foo\" ";
+        let errors = parse_contents_strict(contents, "module_name").unwrap_err();
+        assert_eq!(0..contents.len(), errors[0].span);
+    }
+
+    #[cfg(feature = "fs")]
+    mod fs_tests {
+        use super::*;
+        use std::sync::atomic::{self, AtomicUsize};
+
+        /// A directory under the system's temporary directory that's removed when dropped
+        struct TempDir(PathBuf);
+
+        impl TempDir {
+            /// Creates an empty directory which is unique for this process and call
+            fn new(name: &str) -> Self {
+                static COUNT: AtomicUsize = AtomicUsize::new(0);
+                let count = COUNT.fetch_add(1, atomic::Ordering::Relaxed);
+                let path = std::env::temp_dir().join(format!("clippy_dev_{name}_{}_{count}", std::process::id()));
+                fs::create_dir_all(&path).unwrap();
+                Self(path)
+            }
+
+            /// Writes `contents` to `rel_path` inside the directory, creating any parent
+            /// directories
+            fn write(&self, rel_path: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+                let path = self.0.join(rel_path);
+                fs::create_dir_all(path.parent().unwrap()).unwrap();
+                fs::write(&path, contents).unwrap();
+                path
+            }
+        }
+
+        impl std::ops::Deref for TempDir {
+            type Target = Path;
+
+            fn deref(&self) -> &Path {
+                &self.0
+            }
+        }
+
+        impl AsRef<Path> for TempDir {
+            fn as_ref(&self) -> &Path {
+                &self.0
+            }
+        }
+
+        impl Drop for TempDir {
+            fn drop(&mut self) {
+                drop(fs::remove_dir_all(&self.0));
+            }
+        }
+
+        /// Creates a small lint source tree with lints in nested modules, and one deprecated and
+        /// one renamed lint
+        fn lint_tree() -> TempDir {
+            let root = TempDir::new("lint_tree");
+            root.write("lib.rs", "mod doc;\nmod methods;\nmod ptr;\n");
+            root.write("ptr.rs", "declare_clippy_lint! { pub PTR_ARG, style, \"abc\" }");
+            root.write(
+                "doc.rs",
+                "declare_clippy_lint! { pub DOC_MARKDOWN, pedantic, \"abc\" }\n\
+                 declare_clippy_lint! { pub MISSING_PANICS_DOC, pedantic, \"abc\" }",
+            );
+            root.write(
+                "methods/mod.rs",
+                "mod map_flatten;\ndeclare_clippy_lint! { pub BIND_INSTEAD_OF_MAP, complexity, \"abc\" }",
+            );
+            root.write(
+                "methods/map_flatten.rs",
+                "declare_clippy_lint! { pub MAP_FLATTEN, complexity, \"abc\" }",
+            );
+            root.write(
+                "deprecated_lints.rs",
+                "declare_deprecated_lint! { #[clippy::version = \"1.0.0\"] pub SHOULD_ASSERT_EQ, \"abc\" }",
+            );
+            root.write(
+                "renamed_lints.rs",
+                "pub static RENAMED_LINTS: &[(&str, &str)] = &[\n    (\"clippy::stutter\", \"clippy::module_name_repetitions\"),\n];\n",
+            );
+            root
+        }
 
-        let expected = GENERATED_FILE_COMMENT.to_string()
-            + &[
-                "{",
-                "    store.register_removed(",
-                "        \"clippy::should_assert_eq\",",
-                "        \"has been superseded by should_assert_eq2\",",
-                "    );",
-                "    store.register_removed(",
-                "        \"clippy::another_deprecated\",",
-                "        \"will be removed\",",
-                "    );",
-                "}",
+        #[test]
+        fn test_gen_deprecated() {
+            let lints = vec![
+                DeprecatedLint::new(
+                    "should_assert_eq",
+                    "\"has been superseded by should_assert_eq2\"",
+                    Range::default(),
+                ),
+                DeprecatedLint::new("another_deprecated", "\"will be removed\"", Range::default()),
+            ];
+
+            let expected = GENERATED_FILE_COMMENT.to_string()
+                + &[
+                    "{",
+                    "    store.register_removed(",
+                    "        \"clippy::should_assert_eq\",",
+                    "        \"has been superseded by should_assert_eq2\",",
+                    "    );",
+                    "    store.register_removed(",
+                    "        \"clippy::another_deprecated\",",
+                    "        \"will be removed\",",
+                    "    );",
+                    "}",
+                ]
+                .join("\n")
+                + "\n";
+
+            assert_eq!(expected, gen_deprecated(&lints));
+        }
+
+        #[test]
+        fn test_check_generated() {
+            let dir = TempDir::new("check_generated");
+            let path = dir.write("README.md", "| Lint |\n| ptr_arg |\n");
+
+            assert_eq!(Ok(()), check_generated(&path, || "| Lint |\n| ptr_arg |\n".into()));
+            assert_eq!(
+                Err(Mismatch {
+                    path: path.clone(),
+                    line: 2,
+                    expected: Some("| ptr_arg |".into()),
+                    generated: Some("| box_vec |".into()),
+                }),
+                check_generated(&path, || "| Lint |\n| box_vec |\n".into())
+            );
+            assert_eq!(
+                Err(Mismatch {
+                    path: path.clone(),
+                    line: 3,
+                    expected: Some(String::new()),
+                    generated: Some("| box_vec |".into()),
+                }),
+                check_generated(&path, || "| Lint |\n| ptr_arg |\n| box_vec |\n".into())
+            );
+
+            fs::remove_file(&path).unwrap();
+            assert_eq!(
+                Err(Mismatch {
+                    path: path.clone(),
+                    line: 1,
+                    expected: Some(String::new()),
+                    generated: Some("| Lint |".into()),
+                }),
+                check_generated(&path, || "| Lint |\n".into())
+            );
+        }
+
+        #[test]
+        fn test_glob_match() {
+            assert!(glob_match("methods*", "methods"));
+            assert!(glob_match("methods*", "methods::bind_instead_of_map"));
+            assert!(glob_match("*_map", "methods::bind_instead_of_map"));
+            assert!(glob_match("p?r", "ptr"));
+            assert!(!glob_match("methods*", "loops::for_kv_map"));
+            assert!(!glob_match("p?r", "ptr_arg"));
+        }
+
+        #[test]
+        fn test_lint_source() {
+            struct StaticSource;
+            impl LintSource for StaticSource {
+                fn lints(&self) -> Vec<Lint> {
+                    vec![Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default())]
+                }
+            }
+            assert_eq!(1, LintSet::from(&StaticSource).len());
+
+            let root = lint_tree();
+            let source = FsLintSource {
+                root: root.to_path_buf(),
+            };
+            assert_eq!(gather_all_from(&root).0, source.lints());
+            assert!(LintSet::from(&source).get("ptr_arg").is_some());
+        }
+
+        #[test]
+        fn test_lint_set_update_from_file() {
+            let root = TempDir::new("lint_set_update");
+            let mut lints: LintSet = [
+                Lint::new("ptr_arg", "style", "\"abc\"", "ptr", Range::default()),
+                Lint::new("old_lint", "style", "\"abc\"", "methods", Range::default()),
             ]
-            .join("\n")
-            + "\n";
+            .into_iter()
+            .collect();
+
+            let path = root.write(
+                "methods/mod.rs",
+                "declare_clippy_lint! { pub NEW_LINT, pedantic, \"new\" }",
+            );
+            lints.update_from_file(&root, "methods/mod.rs").unwrap();
+            assert_eq!(2, lints.len());
+            assert!(lints.get("old_lint").is_none());
+            assert_eq!(Some("methods"), lints.get("new_lint").map(|l| &*l.module));
+            assert_eq!(Some("ptr"), lints.get("ptr_arg").map(|l| &*l.module));
+
+            fs::remove_file(path).unwrap();
+            lints.update_from_file(&root, "methods/mod.rs").unwrap();
+            assert_eq!(1, lints.len());
+            assert!(lints.get("new_lint").is_none());
+            assert_eq!(Some("ptr"), lints.get("ptr_arg").map(|l| &*l.module));
+        }
+
+        #[test]
+        fn test_gather_lints_lazy() {
+            let root = lint_tree();
+            let (lints, _, _) = try_gather_all_seq(&root).unwrap();
+            assert_eq!(5, lints.len());
+            assert_eq!(lints, gather_lints_lazy(&root).collect::<Vec<_>>());
+        }
+
+        #[test]
+        fn test_gather_all_sorted() {
+            let lints = gather_all_sorted_from(lint_tree());
+            assert_eq!(
+                vec![
+                    ("doc", "doc_markdown"),
+                    ("doc", "missing_panics_doc"),
+                    ("methods", "bind_instead_of_map"),
+                    ("methods::map_flatten", "map_flatten"),
+                    ("ptr", "ptr_arg"),
+                ],
+                lints
+                    .iter()
+                    .map(|l| (l.module.as_str(), l.name.as_str()))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn test_gather_from_file_encoding() {
+            let dir = TempDir::new("gather_encoding");
+            let declaration = "declare_clippy_lint! { pub FOO, style, \"foo\" }";
+            let path = dir.write("foo.rs", format!("\u{feff}{declaration}"));
+            let mut lints = GatheredLints::default();
+            gather_from_file(Path::new("foo.rs"), &path, &mut lints).unwrap();
+            assert_eq!(1, lints.0.len());
+            assert_eq!(0..declaration.len(), lints.0[0].declaration_range);
+
+            fs::write(&path, b"declare_clippy_lint! { pub FOO, style, \"\xff\" }").unwrap();
+            let error = gather_from_file(Path::new("foo.rs"), &path, &mut lints).unwrap_err();
+            assert!(error.is_invalid_utf8());
+            assert_eq!(path, error.path);
+        }
+
+        #[test]
+        fn test_gather_all_since() {
+            let root = TempDir::new("gather_all_since");
+            root.write("ptr.rs", "declare_clippy_lint! { pub PTR_ARG, style, \"abc\" }");
+            root.write("methods/mod.rs", "");
+
+            let gathered = gather_all_since(&root, SystemTime::UNIX_EPOCH);
+            assert_eq!(
+                vec![root.join("methods/mod.rs"), root.join("ptr.rs")],
+                gathered.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>()
+            );
+            assert!(gathered[0].1.is_empty());
+            assert_eq!("ptr_arg", gathered[1].1[0].name);
+
+            let future = SystemTime::now() + std::time::Duration::from_secs(3600);
+            assert!(gather_all_since(&root, future).is_empty());
+        }
+
+        #[test]
+        fn test_files_without_lints() {
+            let root = TempDir::new("files_without_lints");
+            root.write("ptr.rs", "declare_clippy_lint! { pub PTR_ARG, style, \"abc\" }");
+            let empty = root.write("empty.rs", "// no lints left");
+            root.write("lib.rs", "mod ptr;");
+            let utils = root.write("utils/mod.rs", "pub fn helper() {}");
+            root.write("tests/foo.rs", "");
+
+            let allow: HashSet<_> = ["lib.rs".to_string()].into_iter().collect();
+            assert_eq!(vec![empty, utils], files_without_lints(&root, &allow));
+        }
+
+        #[test]
+        fn test_lint_files_ignored_dirs() {
+            let root = TempDir::new("lint_files_ignored");
+            for file in ["tests/foo.rs", "methods/foo.rs", "fixtures/foo.rs", "lib.rs"] {
+                root.write(file, "");
+            }
+
+            let files = |files: Vec<(PathBuf, DirEntry)>| {
+                files
+                    .into_iter()
+                    .map(|(rel_path, _)| rel_path)
+                    .sorted()
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(
+                vec![
+                    PathBuf::from("fixtures/foo.rs"),
+                    PathBuf::from("lib.rs"),
+                    PathBuf::from("methods/foo.rs"),
+                ],
+                files(lint_files(&root).collect())
+            );
+            assert_eq!(
+                vec![PathBuf::from("lib.rs"), PathBuf::from("tests/foo.rs")],
+                files(lint_files_ignoring(&root, &["methods", "fixtures"]).collect())
+            );
+        }
+
+        #[test]
+        fn test_gather_all_from_roots() {
+            let dir = TempDir::new("gather_roots");
+            let roots = vec![dir.join("upstream"), dir.join("internal")];
+            for (root, group) in ["upstream", "internal"].into_iter().zip(["style", "pedantic"]) {
+                dir.write(
+                    &format!("{root}/{group}.rs"),
+                    format!("declare_clippy_lint! {{ pub {group}_LINT, {group}, \"abc\" }}"),
+                );
+                dir.write(
+                    &format!("{root}/shared.rs"),
+                    format!("declare_clippy_lint! {{ pub SHARED, {group}, \"abc\" }}"),
+                );
+            }
+
+            let lints = gather_all_from_roots(&roots, OnCollision::PreferFirst).unwrap();
+            let names: Vec<_> = lints
+                .iter()
+                .map(|l| (l.name.as_str(), l.group.as_str()))
+                .sorted()
+                .collect();
+            assert_eq!(
+                vec![
+                    ("pedantic_lint", "pedantic"),
+                    ("shared", "style"),
+                    ("style_lint", "style")
+                ],
+                names
+            );
+
+            assert_eq!(
+                Err(LintCollision {
+                    name: "shared".into(),
+                    first: roots[0].clone(),
+                    second: roots[1].clone(),
+                }),
+                gather_all_from_roots(&roots, OnCollision::Error)
+            );
+            assert_eq!(2, gather_all_from_roots(&roots[..1], OnCollision::Error).unwrap().len());
+        }
 
-        assert_eq!(expected, gen_deprecated(&lints));
+        #[test]
+        fn test_gather_from_sources() {
+            let root = lint_tree();
+            let contents = fs::read_to_string(root.join("doc.rs")).unwrap();
+            assert_eq!(
+                lints_in_file(Path::new("doc.rs"), &root.join("doc.rs")),
+                gather_from_sources([("doc", &*contents)])
+            );
+
+            let deprecated = "declare_deprecated_lint! { #[clippy::version = \"1.0.0\"] pub BAR, \"bar\" }";
+            let mut gathered = GatheredLints::default();
+            parse_module("deprecated_lints", deprecated, &mut gathered);
+            assert!(gathered.0.is_empty());
+            assert_eq!(vec!["bar"], gathered.1.iter().map(|l| &*l.name).collect::<Vec<_>>());
+
+            // The `deprecated_lints` module is only searched for deprecated lints
+            let lints = gather_from_sources([
+                ("foo", "declare_clippy_lint! { pub FOO, style, \"foo\" }"),
+                ("deprecated_lints", "declare_clippy_lint! { pub BAR, style, \"bar\" }"),
+            ]);
+            assert_eq!(1, lints.len());
+            assert_eq!("foo", lints[0].name);
+            assert_eq!("foo", lints[0].module);
+        }
+
+        #[test]
+        fn test_module_path() {
+            let root = lint_tree();
+            assert_eq!(Some(root.join("ptr.rs")), module_path(&root, "ptr"));
+            assert_eq!(Some(root.join("methods/mod.rs")), module_path(&root, "methods"));
+            assert_eq!(
+                Some(root.join("methods/map_flatten.rs")),
+                module_path(&root, "methods::map_flatten")
+            );
+            assert_eq!(
+                Some(root.join("methods/map_flatten.rs")),
+                module_path(&root, "map_flatten")
+            );
+            assert_eq!(None, module_path(&root, "missing"));
+
+            // `utils` is declared by both files
+            root.write("utils.rs", "");
+            root.write("utils/mod.rs", "");
+            assert_eq!(Some(root.join("utils/mod.rs")), module_path(&root, "utils"));
+        }
+
+        #[test]
+        fn test_gather_filtered() {
+            let root = lint_tree();
+            let pedantic: Vec<_> = gather_filtered(&root, |l| l.group == "pedantic").collect();
+            assert_eq!(2, pedantic.len());
+            assert_eq!(
+                gather_lints_lazy(&root)
+                    .filter(|l| l.group == "pedantic")
+                    .collect::<Vec<_>>(),
+                pedantic
+            );
+        }
+
+        #[test]
+        #[cfg(feature = "rayon")]
+        fn test_gather_all_par() {
+            fn sorted((mut lints, mut deprecated, renamed): GatheredLints) -> (Vec<Lint>, Vec<DeprecatedLint>, usize) {
+                lints.sort_by(|a, b| (&a.module, &a.name).cmp(&(&b.module, &b.name)));
+                deprecated.sort_by(|a, b| a.name.cmp(&b.name));
+                (lints, deprecated, renamed.len())
+            }
+
+            let root = lint_tree();
+            assert_eq!(
+                sorted(try_gather_all_seq(&root).unwrap()),
+                sorted(try_gather_all_par(&root).unwrap())
+            );
+        }
     }
 }
@@ -0,0 +1,47 @@
+//! Smoke test running the `update_lints` checks against the lints declared in `clippy_lints`. The
+//! unit tests in `update_lints` only use small fixtures.
+
+#![cfg(feature = "fs")]
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use clippy_dev::update_lints::{
+    gather_all_from, lint_files, parse_contents_strict, parse_declared_lints_contents, Lint,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+/// The source directory of `clippy_lints`, relative to this crate
+fn clippy_lints_src() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../clippy_lints/src")
+}
+
+#[test]
+fn clippy_lints_are_consistent() {
+    let root = clippy_lints_src();
+    for (rel_path, file) in lint_files(&root) {
+        let contents = fs::read_to_string(file.path()).unwrap();
+        if let Err(errors) = parse_contents_strict(&contents, &rel_path.to_string_lossy()) {
+            panic!("{}", errors[0]);
+        }
+    }
+
+    let (lints, deprecated_lints, _) = gather_all_from(&root);
+    assert!(!lints.is_empty());
+    Lint::assert_unique_names(&lints).unwrap();
+    assert_eq!(Vec::<&Lint>::new(), Lint::invalid_lint_names(&lints));
+    assert_eq!(
+        Vec::<&Lint>::new(),
+        Lint::inconsistent_deprecation(&lints, &deprecated_lints)
+    );
+
+    let mut registered = HashSet::new();
+    parse_declared_lints_contents(
+        &fs::read_to_string(root.join("declared_lints.rs")).unwrap(),
+        &mut registered,
+    );
+    assert_eq!(
+        Vec::<&Lint>::new(),
+        Lint::lints_missing_group_registration(&lints, &registered)
+    );
+}